@@ -0,0 +1,155 @@
+/// Decoding of the alert flags in the Status register (00h).
+///
+/// The ALRT pin is asserted (pulled low) while any enabled alert flag in Status is set. See
+/// `Max1726x::handle_alert` for reading and clearing the cause.
+use crate::registers::Status;
+
+/// An alert condition reported in the Status register
+#[derive(Debug, Clone, Copy, PartialEq, Eq, defmt::Format)]
+pub enum AlertEvent {
+    /// VCell fell below the VAlrtTh minimum (Status.Vmn)
+    VoltageLow,
+    /// VCell exceeded the VAlrtTh maximum (Status.Vmx)
+    VoltageHigh,
+    /// Temp fell below the TAlrtTh minimum (Status.Tmn)
+    TemperatureLow,
+    /// Temp exceeded the TAlrtTh maximum (Status.Tmx)
+    TemperatureHigh,
+    /// RepSOC fell below the SAlrtTh minimum (Status.Smn)
+    StateOfChargeLow,
+    /// RepSOC exceeded the SAlrtTh maximum (Status.Smx)
+    StateOfChargeHigh,
+    /// Current fell below the IAlrtTh minimum (Status.Imn)
+    CurrentLow,
+    /// Current exceeded the IAlrtTh maximum (Status.Imx)
+    CurrentHigh,
+    /// RepSOC crossed an integer percentage boundary (Status.dSOCi)
+    StateOfChargeStep,
+    /// A battery was inserted (Status.Bi)
+    BatteryInserted,
+    /// The battery was removed (Status.Br)
+    BatteryRemoved,
+    /// A software or hardware POR occurred (Status.POR)
+    PowerOnReset,
+}
+
+impl AlertEvent {
+    /// Every alert event, in Status bit order
+    pub const ALL: [AlertEvent; 12] = [
+        AlertEvent::PowerOnReset,
+        AlertEvent::CurrentLow,
+        AlertEvent::CurrentHigh,
+        AlertEvent::StateOfChargeStep,
+        AlertEvent::VoltageLow,
+        AlertEvent::TemperatureLow,
+        AlertEvent::StateOfChargeLow,
+        AlertEvent::BatteryInserted,
+        AlertEvent::VoltageHigh,
+        AlertEvent::TemperatureHigh,
+        AlertEvent::StateOfChargeHigh,
+        AlertEvent::BatteryRemoved,
+    ];
+
+    /// The Status flag that reports this event
+    pub fn status_flag(self) -> Status {
+        match self {
+            AlertEvent::VoltageLow => Status::VMN,
+            AlertEvent::VoltageHigh => Status::VMX,
+            AlertEvent::TemperatureLow => Status::TMN,
+            AlertEvent::TemperatureHigh => Status::TMX,
+            AlertEvent::StateOfChargeLow => Status::SMN,
+            AlertEvent::StateOfChargeHigh => Status::SMX,
+            AlertEvent::CurrentLow => Status::IMN,
+            AlertEvent::CurrentHigh => Status::IMX,
+            AlertEvent::StateOfChargeStep => Status::D_SOC_I,
+            AlertEvent::BatteryInserted => Status::BI,
+            AlertEvent::BatteryRemoved => Status::BR,
+            AlertEvent::PowerOnReset => Status::POR,
+        }
+    }
+}
+
+/// A set of alert events, decoded from the Status register
+#[derive(Debug, Clone, Copy, PartialEq, Eq, defmt::Format)]
+pub struct AlertEvents {
+    status: Status,
+}
+
+impl AlertEvents {
+    /// No alert events
+    pub fn empty() -> Self {
+        Self {
+            status: Status::empty(),
+        }
+    }
+
+    /// The alert events flagged in a Status register value. Status.Bst is a state rather than an
+    /// event and is not included.
+    pub fn from_status(status: Status) -> Self {
+        let all = AlertEvent::ALL
+            .iter()
+            .fold(Status::empty(), |all, event| all | event.status_flag());
+        Self {
+            status: status & all,
+        }
+    }
+
+    /// The Status flags of these events
+    pub fn status(&self) -> Status {
+        self.status
+    }
+
+    /// Whether there are no events
+    pub fn is_empty(&self) -> bool {
+        self.status.is_empty()
+    }
+
+    /// Whether `event` is in the set
+    pub fn contains(&self, event: AlertEvent) -> bool {
+        self.status.contains(event.status_flag())
+    }
+
+    /// Iterate over the events in the set, in Status bit order
+    pub fn iter(&self) -> impl Iterator<Item = AlertEvent> {
+        let status = self.status;
+        AlertEvent::ALL
+            .into_iter()
+            .filter(move |event| status.contains(event.status_flag()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn alert_events_from_status() {
+        // Status at power-up: POR, Bst, dSOCi and Br
+        let events = AlertEvents::from_status(Status::from_bits_truncate(0x8082));
+        assert!(events.contains(AlertEvent::PowerOnReset));
+        assert!(events.contains(AlertEvent::StateOfChargeStep));
+        assert!(events.contains(AlertEvent::BatteryRemoved));
+        assert!(!events.contains(AlertEvent::VoltageLow));
+        assert_eq!(events.iter().count(), 3);
+
+        // Bst is a state, not an event
+        let events = AlertEvents::from_status(Status::BST | Status::VMN | Status::TMX);
+        assert_eq!(events.status(), Status::VMN | Status::TMX);
+        let mut iter = events.iter();
+        assert_eq!(iter.next(), Some(AlertEvent::VoltageLow));
+        assert_eq!(iter.next(), Some(AlertEvent::TemperatureHigh));
+        assert_eq!(iter.next(), None);
+
+        assert!(AlertEvents::from_status(Status::BST).is_empty());
+    }
+
+    #[test]
+    fn every_event_has_its_own_flag() {
+        for (i, event) in AlertEvent::ALL.iter().enumerate() {
+            assert_eq!(event.status_flag().bits().count_ones(), 1);
+            for other in &AlertEvent::ALL[i + 1..] {
+                assert_ne!(event.status_flag(), other.status_flag());
+            }
+        }
+    }
+}
@@ -5,60 +5,415 @@
 /// and software implementation guide:
 /// https://www.analog.com/media/en/technical-documentation/user-guides/modelgauge-m5-host-side-software-implementation-guide.pdf
 use crate::{
-    error::Error,
-    registers::{FStat, HibCfg, ModelCfg, OutputRegister, Register, SoftWakeup, Status, VEmpty},
-    traits::{BitField, Model, RegisterResolver},
+    alert::AlertEvents,
+    error::{Error, TimeoutStep},
+    learned::{LearnedParameters, RESTORE_DELAY_MS},
+    model::{CustomModel, MODEL_LOCK, MODEL_TABLE_LEN, MODEL_UNLOCK},
+    registers::{
+        Config, Config2, FStat, HibCfg, IAlrtTh, MaxMinCurr, MaxMinTemp, MaxMinVolt, ModelCfg,
+        OutputRegister, PackCfg, RCell, Register, SAlrtTh, SoftWakeup, Status, Status2,
+        StickyAlerts, TAlrtTh, TemperatureSource, Tte, Ttf, VAlrtTh, VEmpty, VFocv,
+    },
+    traits::{
+        BitField, Capacity, Current, Cycles, Model, MultiCell, Percentage, Power, RegisterResolver,
+        Resistance, Temperature, Units, Voltage,
+    },
+    units::Float,
 };
 use core::fmt::Debug;
+use core::time::Duration;
 use defmt::debug;
 use embedded_hal::delay::DelayNs;
+use embedded_hal::digital::{self, InputPin};
 use embedded_hal::i2c;
 
 #[derive(Debug, defmt::Format)]
-pub struct Max1726x<'a, M, I2C, R>
+pub struct Max1726x<M, I2C, R>
 where
     R: RegisterResolver,
 {
-    i2c: &'a mut I2C,
+    i2c: I2C,
     register_resolver: R,
+    timeouts: Timeouts,
     _phantom: core::marker::PhantomData<M>,
 }
 
 // The MAX1726x supports the slave address 0x6C
 // The datasheet specifies an I2C slave address of 0x6C, i.e. 01101100
 // For the HAL, you need to remove the LSB, which turns it into 0110110 or 0x36
-const ADDR: u8 = 0x36;
+pub(crate) const ADDR: u8 = 0x36;
+
+/// Number of retries before a write is reported as not verified
+pub(crate) const WRITE_VERIFY_RETRIES: u8 = 3;
+
+/// Interval between polls of a status bit, in milliseconds
+pub(crate) const POLL_INTERVAL_MS: u32 = 10;
+
+/// The register writes that exit hibernate mode in step 2: Soft-Wakeup, HibCfg = 0, then clear
+/// the command. Shared by the blocking and async drivers.
+pub(crate) const EXIT_HIBERNATE: [(u8, u16); 3] = [
+    (Register::SOFT_WAKEUP, SoftWakeup::SOFT_WAKEUP),
+    (Register::HIB_CFG, 0),
+    (Register::SOFT_WAKEUP, SoftWakeup::CLEAR),
+];
+
+/// The Status value that clears POR in step 3, keeping the other flags
+pub(crate) fn status_without_por(status: u16) -> u16 {
+    status & !Status::POR.bits()
+}
+
+/// Largest number of cells in series PackCfg.NCELLS can be set to
+pub const MAX_CELLS: u8 = 15;
+
+/// Maximum wait times for the polling loops in `ez_config`. If the gauge is missing, stuck or
+/// disconnected mid-sequence, the loop gives up with `Error::Timeout` instead of hanging.
+#[derive(Debug, Clone, Copy, defmt::Format)]
+pub struct Timeouts {
+    /// Maximum time to wait for FStat.DNR to clear, in milliseconds. DNR clears 710ms after
+    /// power-up.
+    pub data_ready_ms: u32,
+    /// Maximum time to wait for ModelCfg.Refresh to clear, in milliseconds
+    pub model_refresh_ms: u32,
+}
+
+impl Default for Timeouts {
+    fn default() -> Self {
+        Self {
+            data_ready_ms: 2000,
+            model_refresh_ms: 2000,
+        }
+    }
+}
+
+/// Tracks the time spent in a polling loop
+pub(crate) struct PollTimer {
+    step: TimeoutStep,
+    limit_ms: u32,
+    waited_ms: u32,
+}
+
+impl PollTimer {
+    pub(crate) fn new(step: TimeoutStep, limit_ms: u32) -> Self {
+        Self {
+            step,
+            limit_ms,
+            waited_ms: 0,
+        }
+    }
+
+    /// Account for another poll, returning the time to wait before it, or a timeout error once the
+    /// limit has been reached
+    pub(crate) fn tick<E>(&mut self) -> Result<u32, Error<E>>
+    where
+        E: i2c::Error,
+    {
+        if self.waited_ms >= self.limit_ms {
+            return Err(Error::Timeout(self.step));
+        }
+        self.waited_ms = self.waited_ms.saturating_add(POLL_INTERVAL_MS);
+        Ok(POLL_INTERVAL_MS)
+    }
+}
+
+/// Build the I2C payload for a register write - data is sent little endian/LSB first
+pub(crate) fn write_payload(register: u8, data: u16) -> [u8; 3] {
+    [register, (data & 0xFF) as u8, ((data & 0xFF00) >> 8) as u8]
+}
+
+/// Convert the bytes read from a register (LSB first) into a u16
+pub(crate) fn register_bytes_to_u16(data: [u8; 2]) -> u16 {
+    (u16::from(data[1]) << 8) | u16::from(data[0])
+}
 
 /// EzConfig struct - see step 2.1 (page 7) of ModelGauge m5 Host Side Software
 /// Implementation Guide UG6595; Rev 4; 12/21
+///
+/// The quantities are in the units of the register resolver, e.g. `Volts` with the float
+/// resolvers and `fixed::MilliVolts` with the fixed-point resolver.
 #[derive(Debug, defmt::Format)]
-pub struct EzConfig {
-    /// * `charge_voltage`: in millivolts
-    pub charge_voltage_mv: u16,
-    /// * `design_cap`: the expected capacity of the cell in mAh
-    pub design_cap_mah: u16,
-    /// * `i_chg_term`: in milliamps
-    pub i_chg_term_ma: u16,
+pub struct EzConfig<U: Units = Float> {
+    /// * `charge_voltage`: the charge voltage of the cell
+    pub charge_voltage: U::Voltage,
+    /// * `design_cap`: the expected capacity of the cell
+    pub design_cap: U::Capacity,
+    /// * `i_chg_term`: the charge termination current
+    pub i_chg_term: U::Current,
     /// * `v_empty`: see VEmpty struct configuration
-    pub v_empty_mv: VEmpty,
+    pub v_empty: VEmpty,
 }
 
-/// Battery charge status
-#[derive(Debug, defmt::Format)]
-pub struct BatteryChargeStatus {
-    /// RepCap or reported remaining capacity in mAh.
-    rep_cap: u16,
+/// VCell register value of 4.275V, above which the EZ config selects the high-voltage model
+const HIGH_CHARGE_VOLTAGE: u16 = 54720;
+
+impl<U: Units> EzConfig<U> {
+    /// The register writes for "2.1 OPTION 1 EZ Config", in the order they must be made.
+    /// Shared by the blocking and async drivers so both configure the chip identically.
+    pub(crate) fn register_writes<R>(&self, register_resolver: &R) -> [(u8, u16); 4]
+    where
+        R: RegisterResolver<Units = U>,
+    {
+        let model_cfg =
+            if register_resolver.voltage_to_register(self.charge_voltage) > HIGH_CHARGE_VOLTAGE {
+                0x8400
+            } else {
+                0x8000
+            };
+        [
+            (
+                Register::DESIGN_CAP,
+                register_resolver.capacity_to_register(self.design_cap),
+            ),
+            (
+                Register::I_CHG_TERM,
+                register_resolver.current_to_register(self.i_chg_term),
+            ),
+            (
+                Register::V_EMPTY,
+                u16::from_le_bytes(self.v_empty.into_bytes()),
+            ),
+            (Register::MODEL_CFG, model_cfg),
+        ]
+    }
+}
+
+/// Lower and upper limits of an alert threshold register, in physical units. `None` disables
+/// that limit.
+#[derive(Debug, Clone, Copy, Default, PartialEq, defmt::Format)]
+pub struct AlertThresholds<T> {
+    /// An alert is generated if the value falls below this limit
+    pub min: Option<T>,
+    /// An alert is generated if the value exceeds this limit
+    pub max: Option<T>,
+}
+
+impl<T> AlertThresholds<T> {
+    /// Both limits disabled
+    pub const DISABLED: Self = Self {
+        min: None,
+        max: None,
+    };
+
+    /// Convert the threshold bytes of an alert register, treating the disable values as `None`
+    fn from_bytes<F>(min: u8, max: u8, disabled: (u8, u8), to_physical: F) -> Self
+    where
+        F: Fn(u16) -> T,
+    {
+        Self {
+            min: (min != disabled.0).then(|| to_physical(threshold_to_register(min))),
+            max: (max != disabled.1).then(|| to_physical(threshold_to_register(max))),
+        }
+    }
+}
+
+/// Voltage, current, temperature and power of the cell, either instant or averaged
+#[derive(Debug, Clone, Copy, PartialEq, defmt::Format)]
+pub struct Measurement<U: Units = Float> {
+    /// Cell voltage
+    pub voltage: U::Voltage,
+    /// Charge (positive) or discharge (negative) current
+    pub current: U::Current,
+    /// Temperature
+    pub temperature: U::Temperature,
+    /// Charge (positive) or discharge (negative) power
+    pub power: U::Power,
+}
+
+/// First register of a snapshot (Status)
+const SNAPSHOT_FIRST: u8 = Register::STATUS;
+/// Number of registers in a snapshot, up to TTF (20h)
+const SNAPSHOT_LEN: usize = 0x21;
+
+/// The ModelGauge outputs, read in one sequential transaction so they are taken at the same
+/// instant
+#[derive(Debug, Clone, Copy, PartialEq, defmt::Format)]
+pub struct Snapshot<U: Units = Float> {
+    /// Reported remaining capacity (RepCap)
+    pub rep_cap: U::Capacity,
+    /// Reported state of charge (RepSOC)
+    pub rep_soc: U::Percentage,
+    /// Full capacity that goes with RepCap (FullCapRep)
+    pub full_cap_rep: U::Capacity,
+    /// Estimated time to empty (TTE), `None` if not discharging
+    pub tte: Option<Duration>,
+    /// Estimated time to full (TTF), `None` if not charging
+    pub ttf: Option<Duration>,
+    /// Cell voltage (VCell)
+    pub voltage: U::Voltage,
+    /// Average cell voltage (AvgVCell)
+    pub average_voltage: U::Voltage,
+    /// Charge/discharge current (Current)
+    pub current: U::Current,
+    /// Average charge/discharge current (AvgCurrent)
+    pub average_current: U::Current,
+    /// Temperature (Temp)
+    pub temperature: U::Temperature,
+    /// Number of full charge/discharge cycles, in 16% steps (Cycles)
+    pub cycles: U::Cycles,
+    /// Present capacity compared to the design capacity (Age)
+    pub age: U::Percentage,
+    /// Status Register (00h)
+    pub status: Status,
+}
+
+/// Lowest and highest values logged by a MaxMin register since it was last reset, in physical
+/// units
+#[derive(Debug, Clone, Copy, PartialEq, defmt::Format)]
+pub struct MaxMin<T> {
+    /// Lowest value logged
+    pub min: T,
+    /// Highest value logged
+    pub max: T,
+}
+
+impl<T> MaxMin<T> {
+    /// Convert the bytes of a MaxMin register, `None` if it still holds its reset value
+    fn from_bytes<F>(min: u8, max: u8, reset: (u8, u8), to_physical: F) -> Option<Self>
+    where
+        F: Fn(u16) -> T,
+    {
+        ((min, max) != reset).then(|| Self {
+            min: to_physical(threshold_to_register(min)),
+            max: to_physical(threshold_to_register(max)),
+        })
+    }
+}
+
+/// The alert threshold registers hold the upper byte of the matching measurement register.
+/// Round an unsigned register value (VCell, RepSOC) to its threshold byte.
+fn unsigned_threshold(register: u16) -> u8 {
+    ((u32::from(register) + 0x80) >> 8).min(0xFF) as u8
+}
+
+/// Round a two's-complement register value (Temp, Current) to its threshold byte
+fn signed_threshold(register: u16) -> u8 {
+    ((i32::from(register as i16) + 0x80) >> 8).clamp(-128, 127) as i8 as u8
+}
+
+/// The register value a threshold byte corresponds to
+fn threshold_to_register(threshold: u8) -> u16 {
+    u16::from(threshold) << 8
+}
+
+/// Convert a TTE or TTF register value to a duration. The gauge reports 0xFFFF when the
+/// estimate does not apply, e.g. TTF while discharging. The LSB doesn't depend on the sense
+/// resistor, so this uses integer arithmetic whatever the resolver.
+fn register_to_duration(register: u16) -> Option<Duration> {
+    Ttf::from(register).to_duration()
+}
+
+/// Whether the battery is charging or discharging
+#[derive(Debug, Clone, Copy, PartialEq, Eq, defmt::Format)]
+pub enum ChargeState {
+    /// AvgCurrent is positive
+    Charging,
+    /// AvgCurrent is negative
+    Discharging,
+    /// The cell is relaxed (FStat.RelDt) or AvgCurrent is zero
+    Relaxed,
+}
+
+/// Battery charge status, converted to physical units
+#[derive(Debug, Clone, Copy, PartialEq, defmt::Format)]
+pub struct BatteryChargeStatus<U: Units = Float> {
+    /// RepCap or reported remaining capacity
+    pub rep_cap: U::Capacity,
 
     /// RepSOC is the reported state-of-charge percentage output
-    rep_soc: u16,
+    pub rep_soc: U::Percentage,
+
+    /// FullCapRep is the full capacity that goes with RepCap
+    pub full_cap_rep: U::Capacity,
+
+    /// TTE is the estimated time to empty for the application under present temperature and
+    /// load conditions, `None` if not discharging
+    pub tte: Option<Duration>,
+
+    /// TTF is the estimated time to full for the application under present conditions, `None`
+    /// if not charging
+    pub ttf: Option<Duration>,
+
+    /// Whether the battery is charging or discharging
+    pub state: ChargeState,
+
+    raw: RawChargeStatus,
+}
+
+impl<U: Units> BatteryChargeStatus<U> {
+    /// The register values the status was converted from
+    pub fn raw(&self) -> &RawChargeStatus {
+        &self.raw
+    }
+}
+
+/// Cycle count and capacity fade of the cell, for tracking its health in the field
+#[derive(Debug, Clone, Copy, PartialEq, defmt::Format)]
+pub struct StateOfHealth<U: Units = Float> {
+    /// Number of full charge/discharge cycles, in 16% steps (Cycles)
+    pub cycles: U::Cycles,
+    /// Expected capacity of the cell (DesignCap)
+    pub design_cap: U::Capacity,
+    /// Present full capacity of the cell, without temperature and empty compensation
+    /// (FullCapNom)
+    pub full_cap_nom: U::Capacity,
+    /// Present capacity compared to DesignCap (Age)
+    pub age: U::Percentage,
+    /// Capacity lost compared to DesignCap, i.e. 100% - Age
+    pub capacity_fade: U::Percentage,
+    /// Forecast number of cycles left (AgeForecast - Cycles). `None` if the part doesn't forecast
+    /// its cycle life (only the MAX17263 does) or hasn't made a forecast yet.
+    pub remaining_cycles: Option<U::Cycles>,
+}
 
-    /// TTE is the estimated time to empty for the application under present
-    /// temperature and load conditions. The TTE value is determined by relating AvCap with
-    /// The LSB of the TTE register is 5.625s.
-    tte: u16,
+/// Age register value of 100%
+const AGE_FULL: u16 = 100 << 8;
+
+/// The register values of a `BatteryChargeStatus`, for debugging
+#[derive(Debug, Clone, Copy, PartialEq, Eq, defmt::Format)]
+pub struct RawChargeStatus {
+    /// RepCap Register (05h)
+    pub rep_cap: u16,
+    /// RepSOC Register (06h)
+    pub rep_soc: u16,
+    /// FullCapRep Register (10h)
+    pub full_cap_rep: u16,
+    /// TTE Register (11h)
+    pub tte: u16,
+    /// TTF Register (20h)
+    pub ttf: u16,
+    /// AvgCurrent Register (0Bh)
+    pub avg_current: u16,
+    /// FStat Register (3Dh)
+    pub f_stat: u16,
+}
+
+impl RawChargeStatus {
+    /// Convert the register values to physical units
+    pub(crate) fn resolve<R>(self, register_resolver: &R) -> BatteryChargeStatus<R::Units>
+    where
+        R: RegisterResolver,
+    {
+        let avg_current = self.avg_current as i16;
+        let state =
+            if FStat::from_bits_truncate(self.f_stat).contains(FStat::REL_DT) || avg_current == 0 {
+                ChargeState::Relaxed
+            } else if avg_current > 0 {
+                ChargeState::Charging
+            } else {
+                ChargeState::Discharging
+            };
+        BatteryChargeStatus {
+            rep_cap: register_resolver.register_to_capacity(self.rep_cap),
+            rep_soc: register_resolver.register_to_percentage(self.rep_soc),
+            full_cap_rep: register_resolver.register_to_capacity(self.full_cap_rep),
+            tte: register_to_duration(self.tte),
+            ttf: register_to_duration(self.ttf),
+            state,
+            raw: self,
+        }
+    }
 }
 
-impl<'a, M, I2C, E, R> Max1726x<'a, M, I2C, R>
+impl<M, I2C, E, R> Max1726x<M, I2C, R>
 where
     M: Model,
     I2C: i2c::I2c<Error = E>,
@@ -66,17 +421,41 @@ where
     R: RegisterResolver,
 {
     /// Create a new driver instance.
-    pub fn new(i2c: &'a mut I2C, register_resolver: R) -> Self {
+    ///
+    /// The bus is taken by value. Pass `&mut i2c` to keep ownership, or an `embedded-hal-bus`
+    /// device (e.g. `RefCellDevice`, `CriticalSectionDevice`) to share the bus with other drivers.
+    pub fn new(i2c: I2C, register_resolver: R) -> Self {
         Self {
             i2c,
             register_resolver,
+            timeouts: Timeouts::default(),
             _phantom: core::marker::PhantomData,
         }
     }
 
+    /// Set the maximum wait times for the polling loops in `ez_config`
+    pub fn set_timeouts(&mut self, timeouts: Timeouts) {
+        self.timeouts = timeouts;
+    }
+
+    /// Get the maximum wait times for the polling loops in `ez_config`
+    pub fn timeouts(&self) -> Timeouts {
+        self.timeouts
+    }
+
+    /// Destroy the driver instance, returning the I2C bus and the register resolver.
+    pub fn release(self) -> (I2C, R) {
+        (self.i2c, self.register_resolver)
+    }
+
+    /// Destroy the driver instance, returning the I2C bus.
+    pub fn destroy(self) -> I2C {
+        self.i2c
+    }
+
     /// Write a register - data should be written little endian/LSB first
     pub fn write_register(&mut self, register: u8, data: u16) -> Result<(), Error<E>> {
-        let payload: [u8; 3] = [register, (data & 0xFF) as u8, ((data & 0xFF00) >> 8) as u8];
+        let payload = write_payload(register, data);
         debug!("Data {:X} written to register {:X}", data, register);
         self.i2c.write(ADDR, &payload).map_err(Error::I2c)
     }
@@ -99,7 +478,7 @@ where
                 return Ok(());
             }
             attempt += 1;
-            if attempt > 3 {
+            if attempt > WRITE_VERIFY_RETRIES {
                 return Err(Error::WriteNotVerified {
                     register,
                     write: data,
@@ -132,10 +511,21 @@ where
         data
     }
 
+    /// Read `N` consecutive registers in one transaction, starting at `register`. The gauge
+    /// auto-increments the register address after every word.
+    pub fn read_registers<const N: usize>(&mut self, register: u8) -> Result<[u16; N], Error<E>> {
+        let mut data = [[0u8; 2]; N];
+        self.i2c
+            .write_read(ADDR, &[register], data.as_flattened_mut())
+            .map_err(Error::I2c)?;
+        debug!("{} registers read from register {:X}", N, register);
+        Ok(data.map(register_bytes_to_u16))
+    }
+
     /// Read a register into a u16
     pub fn read_register_as_u16(&mut self, register: u8) -> Result<u16, Error<E>> {
         let data = self.read_register(register)?;
-        Ok((u16::from(data[1]) << 8) | u16::from(data[0]))
+        Ok(register_bytes_to_u16(data))
     }
 
     /// Read a register and convert to the given bitfield
@@ -146,36 +536,184 @@ where
         Ok(B::from(self.read_register_as_u16(B::REGISTER)?))
     }
 
+    /// Read the register of `B`, apply `f` and write the result back, e.g.
+    /// `gauge.modify(|config: Config| config.with_aen(true))`.
+    ///
+    /// The driver holds `&mut self` for the whole read-modify-write, so no other access through it
+    /// can come in between. The fields `f` doesn't change, including the reserved bits, keep the
+    /// value the chip reported.
+    pub fn modify<B, F>(&mut self, f: F) -> Result<(), Error<E>>
+    where
+        B: From<u16> + Into<u16> + BitField,
+        F: FnOnce(B) -> B,
+    {
+        let bitfield = self.read_register_as_bitfield::<B>()?;
+        self.write_bitfield_to_register(f(bitfield))
+    }
+
+    /// Like `modify`, but read the register back after the write with `write_and_verify_register`
+    pub fn modify_verified<B, F, D>(&mut self, delay: D, f: F) -> Result<(), Error<E>>
+    where
+        B: From<u16> + Into<u16> + BitField,
+        F: FnOnce(B) -> B,
+        D: DelayNs,
+    {
+        let bitfield = self.read_register_as_bitfield::<B>()?;
+        self.write_and_verify_register(B::REGISTER, f(bitfield).into(), delay)
+    }
+
     /// Battery voltage in V
-    pub fn battery_voltage(&mut self) -> Result<f64, Error<E>> {
+    pub fn battery_voltage(&mut self) -> Result<Voltage<R>, Error<E>> {
         let register = self.read_register_as_u16(M::V_CELL)?;
         Ok(self.register_resolver.register_to_voltage(register))
     }
 
     /// Battery charge/discharge current in A
-    pub fn battery_current(&mut self) -> Result<f64, Error<E>> {
+    pub fn battery_current(&mut self) -> Result<Current<R>, Error<E>> {
         let register = self.read_register_as_u16(M::CURRENT)?;
         Ok(self.register_resolver.register_to_current(register))
     }
 
     /// Battery temperature in degrees C
-    pub fn battery_temperature(&mut self) -> Result<f64, Error<E>> {
+    pub fn battery_temperature(&mut self) -> Result<Temperature<R>, Error<E>> {
         let register = self.read_register_as_u16(M::TEMP)?;
         Ok(self.register_resolver.register_to_temperature(register))
     }
 
     /// Battery state of charge as a percentage
-    pub fn battery_state_of_charge(&mut self) -> Result<f64, Error<E>> {
+    pub fn battery_state_of_charge(&mut self) -> Result<Percentage<R>, Error<E>> {
         let register = self.read_register_as_u16(OutputRegister::REP_SOC)?;
         Ok(self.register_resolver.register_to_percentage(register))
     }
 
+    /// Get TTE Register (11h)
+    pub fn tte_register(&mut self) -> Result<Tte, Error<E>> {
+        self.read_register_as_bitfield::<Tte>()
+    }
+
+    /// Get TTF Register (20h)
+    pub fn ttf_register(&mut self) -> Result<Ttf, Error<E>> {
+        self.read_register_as_bitfield::<Ttf>()
+    }
+
+    /// Estimated time to empty (TTE), `None` if the battery is not discharging
+    pub fn time_to_empty(&mut self) -> Result<Option<Duration>, Error<E>> {
+        Ok(self.tte_register()?.to_duration())
+    }
+
+    /// Estimated time to full (TTF), `None` if the battery is not charging
+    pub fn time_to_full(&mut self) -> Result<Option<Duration>, Error<E>> {
+        Ok(self.ttf_register()?.to_duration())
+    }
+
+    /// Get RCell Register (14h)
+    pub fn r_cell_register(&mut self) -> Result<RCell, Error<E>> {
+        self.read_register_as_bitfield::<RCell>()
+    }
+
+    /// Get VFOCV Register (FBh)
+    pub fn v_focv_register(&mut self) -> Result<VFocv, Error<E>> {
+        self.read_register_as_bitfield::<VFocv>()
+    }
+
+    /// Calculated internal resistance of the cell (RCell)
+    pub fn cell_resistance(&mut self) -> Result<Resistance<R>, Error<E>> {
+        let r_cell = self.r_cell_register()?;
+        Ok(self
+            .register_resolver
+            .register_to_resistance(r_cell.resistance()))
+    }
+
+    /// Calculated open-circuit voltage of the cell (VFOCV)
+    pub fn open_circuit_voltage(&mut self) -> Result<Voltage<R>, Error<E>> {
+        let v_focv = self.v_focv_register()?;
+        Ok(self.register_resolver.register_to_voltage(v_focv.voltage()))
+    }
+
+    /// Battery power in W
+    pub fn battery_power(&mut self) -> Result<Power<R>, Error<E>> {
+        let register = self.read_register_as_u16(Register::POWER)?;
+        Ok(self.register_resolver.register_to_power(register))
+    }
+
+    /// Average battery voltage in V (AvgVCell)
+    pub fn battery_average_voltage(&mut self) -> Result<Voltage<R>, Error<E>> {
+        let register = self.read_register_as_u16(Register::AVG_V_CELL)?;
+        Ok(self.register_resolver.register_to_voltage(register))
+    }
+
+    /// Average battery charge/discharge current in A (AvgCurrent)
+    pub fn battery_average_current(&mut self) -> Result<Current<R>, Error<E>> {
+        let register = self.read_register_as_u16(Register::AVG_CURRENT)?;
+        Ok(self.register_resolver.register_to_current(register))
+    }
+
+    /// Average battery temperature in degrees C (AvgTA)
+    pub fn battery_average_temperature(&mut self) -> Result<Temperature<R>, Error<E>> {
+        let register = self.read_register_as_u16(Register::AVG_TA)?;
+        Ok(self.register_resolver.register_to_temperature(register))
+    }
+
+    /// Average battery power in W (AvgPower). The time constant is set by Config2.POWR.
+    pub fn battery_average_power(&mut self) -> Result<Power<R>, Error<E>> {
+        let register = self.read_register_as_u16(Register::AVG_POWER)?;
+        Ok(self.register_resolver.register_to_power(register))
+    }
+
+    /// Read the ModelGauge outputs in a single bus transaction (Status to TTF)
+    pub fn snapshot(&mut self) -> Result<Snapshot<R::Units>, Error<E>> {
+        let registers = self.read_registers::<SNAPSHOT_LEN>(SNAPSHOT_FIRST)?;
+        let register = |address: u8| registers[usize::from(address - SNAPSHOT_FIRST)];
+        let resolver = &self.register_resolver;
+        Ok(Snapshot {
+            rep_cap: resolver.register_to_capacity(register(OutputRegister::REP_CAP)),
+            rep_soc: resolver.register_to_percentage(register(OutputRegister::REP_SOC)),
+            full_cap_rep: resolver.register_to_capacity(register(Register::FULL_CAP_REP)),
+            tte: register_to_duration(register(OutputRegister::TTE)),
+            ttf: register_to_duration(register(Register::TTF)),
+            voltage: resolver.register_to_voltage(register(M::V_CELL)),
+            average_voltage: resolver.register_to_voltage(register(Register::AVG_V_CELL)),
+            current: resolver.register_to_current(register(M::CURRENT)),
+            average_current: resolver.register_to_current(register(Register::AVG_CURRENT)),
+            temperature: resolver.register_to_temperature(register(M::TEMP)),
+            cycles: resolver.register_to_cycles(register(Register::CYCLES)),
+            age: resolver.register_to_percentage(register(Register::AGE)),
+            status: Status::from_bits_truncate(register(Register::STATUS)),
+        })
+    }
+
+    /// Instant voltage, current, temperature and power (VCell, Current, Temp, Power)
+    pub fn measurement(&mut self) -> Result<Measurement<R::Units>, Error<E>> {
+        Ok(Measurement {
+            voltage: self.battery_voltage()?,
+            current: self.battery_current()?,
+            temperature: self.battery_temperature()?,
+            power: self.battery_power()?,
+        })
+    }
+
+    /// Averaged voltage, current, temperature and power (AvgVCell, AvgCurrent, AvgTA, AvgPower)
+    pub fn average_measurement(&mut self) -> Result<Measurement<R::Units>, Error<E>> {
+        Ok(Measurement {
+            voltage: self.battery_average_voltage()?,
+            current: self.battery_average_current()?,
+            temperature: self.battery_average_temperature()?,
+            power: self.battery_average_power()?,
+        })
+    }
+
     /// Get Status register (00h)
     pub fn status_register(&mut self) -> Result<Status, Error<E>> {
         Ok(Status::from_bits_truncate(
             self.read_register_as_u16(Register::STATUS)?,
         ))
     }
+    /// Get Status2 register (B0h)
+    pub fn status_2_register(&mut self) -> Result<Status2, Error<E>> {
+        Ok(Status2::from_bits_truncate(
+            self.read_register_as_u16(Register::STATUS_2)?,
+        ))
+    }
     /// Get FStat Register (3Dh)
     pub fn fstat_register(&mut self) -> Result<FStat, Error<E>> {
         Ok(FStat::from_bits_truncate(
@@ -194,116 +732,1688 @@ where
         Ok(VEmpty::from_bytes(msb_bytes.to_le_bytes()))
     }
 
-    /// Get the battery charge status
-    pub fn battery_charge_status(&mut self) -> Result<BatteryChargeStatus, Error<E>> {
-        let rep_cap = self.read_register_as_u16(OutputRegister::REP_CAP)?;
-        let rep_soc = self.read_register_as_u16(OutputRegister::REP_SOC)?;
-        let tte = self.read_register_as_u16(OutputRegister::TTE)?;
-        Ok(BatteryChargeStatus {
-            rep_cap,
-            rep_soc,
-            tte,
-        })
+    /// Get Config Register (1Dh)
+    pub fn config_register(&mut self) -> Result<Config, Error<E>> {
+        self.read_register_as_bitfield::<Config>()
     }
 
-    /// Setup the fuel gauge as per:
-    /// ModelGauge m5 Host Side Software Implementation Guide UG6595; Rev 4; 12/21
-    /// https://www.analog.com/media/en/technical-documentation/user-guides/modelgauge-m5-host-side-software-implementation-guide.pdf
-    /// page 6
-    pub fn ez_config<D>(&mut self, mut delay: D, ez_config: EzConfig) -> Result<(), Error<E>>
-    where
-        D: DelayNs,
-    {
-        defmt::info!(
-            "Starting MAX1726x EZ Config with configuration: {}",
-            ez_config
-        );
-        // Step 0: check for POR
-        defmt::info!("Checking for Power On Reset (POR)");
-        let status = self.status_register()?;
-        defmt::info!("Initial status: {}", status);
-        let status_por = status & Status::POR;
-        if status_por.is_empty() {
-            defmt::info!("Power On Reset (POR) not detected.");
-            // Go to step 3.2
-        } else {
-            // Do steps 1-2
+    /// Get Config2 Register (BBh)
+    pub fn config_2_register(&mut self) -> Result<Config2, Error<E>> {
+        self.read_register_as_bitfield::<Config2>()
+    }
 
-            // Step 1. Delay until FSTAT.DNR bit == 0
-            defmt::info!("Delaying for DNR bit to clear...");
-            while !(self.fstat_register()? & FStat::DNR).is_empty() {
-                // 10ms Wait Loop. Do not continue until FSTAT.DNR==0
-                delay.delay_ms(10);
-            }
+    /// Enable or disable the ALRT pin for threshold alerts (Config.Aen). The Status register
+    /// flags are updated either way.
+    pub fn set_alerts_enabled(&mut self, enabled: bool) -> Result<(), Error<E>> {
+        self.modify(|config: Config| config.with_aen(enabled))
+    }
 
-            defmt::info!("DNR bit cleared. Proceeding to Step 2.");
-            // Step 2. Initialise configuration
-            // Store original HibCFG value
-            let hib_cfg = self.hib_cfg_register()?;
-            defmt::info!("HibCFG: {}", hib_cfg);
-            defmt::info!("Exiting Hibernate Mode");
-            // Exit Hibernate Mode step 1
-            self.write_register(Register::SOFT_WAKEUP, SoftWakeup::SOFT_WAKEUP)?;
-            // Exit Hibernate Mode step 2
-            self.write_register(Register::HIB_CFG, 0)?;
-            // Exit Hibernate Mode step 3
-            self.write_register(Register::SOFT_WAKEUP, SoftWakeup::CLEAR)?;
-
-            // 2.1 OPTION 1 EZ Config (No INI file is needed):
-            defmt::info!("Option 1 EZ Config");
-            defmt::info!("Writing DESIGN_CAP, I_CHG_TERM, V_EMPTY");
-            self.write_register(
-                Register::DESIGN_CAP,
-                self.register_resolver
-                    .capacity_to_register(ez_config.design_cap_mah as f64 / 1000.0),
-            )?;
-            self.write_register(
-                Register::I_CHG_TERM,
-                self.register_resolver
-                    .current_to_register(ez_config.i_chg_term_ma as f64 / 1000.0),
-            )?;
-            self.write_register(
-                Register::V_EMPTY,
-                u16::from_le_bytes(ez_config.v_empty_mv.into_bytes()),
-            )?;
-            defmt::info!("Writing ModelCFG");
-            if ez_config.charge_voltage_mv > 4275 {
-                self.write_register(0xDB, 0x8400)?; // Write ModelCFG
-            } else {
-                self.write_register(0xDB, 0x8000)?; // Write ModelCFG
-            }
+    /// Enable or disable alerts on battery insertion (Config.Bei) and removal (Config.Ber)
+    pub fn set_battery_detection_alerts(
+        &mut self,
+        insertion: bool,
+        removal: bool,
+    ) -> Result<(), Error<E>> {
+        self.modify(|config: Config| config.with_bei(insertion).with_ber(removal))
+    }
 
-            // Poll ModelCFG.Refresh(highest bit),
-            // proceed to Step 3 when ModelCFG.Refresh=0.
-            defmt::info!("Waiting for ModelCFG.Refresh to clear...");
-            while self.read_register_as_bitfield::<ModelCfg>()?.refresh() {
-                delay.delay_ms(10);
-            }
-            // do not continue until ModelCFG.Refresh==0
+    /// Select which alerts stay set until cleared by software (Config.IS, VS, TS and SS)
+    pub fn set_sticky_alerts(&mut self, sticky: StickyAlerts) -> Result<(), Error<E>> {
+        self.modify(|config: Config| config.with_sticky_alerts(sticky))
+    }
 
-            // Restore Original HibCFG value
-            defmt::info!("Restoring Hibernate Mode");
-            self.write_bitfield_to_register(hib_cfg)?;
+    /// Get the temperature source (Config.TSel, ETHRM, TEn and TEx)
+    pub fn temperature_source(&mut self) -> Result<TemperatureSource, Error<E>> {
+        Ok(self.config_register()?.temperature_source())
+    }
 
-            // Proceed to Step 3.
-        }
-        defmt::info!("Proceeding to Step 3.");
-        // Step 3: Initialization Complete
-        // Clear the POR bit to indicate that the custom model and parameters are successfully loaded.
-        // Read Status
-        defmt::info!("Clearing Power On Reset (POR) bit");
-        let status = self.read_register_as_u16(Register::STATUS)?;
-        self.write_register(Register::STATUS, status & 0xFFFD)?;
-        // Write and Verify Status with POR bit Cleared
-        defmt::info!("Verifying status with cleared POR bit");
-        // Write and Verify Status with POR bit Cleared
-        self.write_and_verify_register(Register::STATUS, status & 0xFFFD, delay)?;
-        defmt::info!("Initialization Complete");
+    /// Select the temperature source (Config.TSel, ETHRM, TEn and TEx)
+    pub fn set_temperature_source(&mut self, source: TemperatureSource) -> Result<(), Error<E>> {
+        self.modify(|config: Config| config.with_temperature_source(source))
+    }
 
-        defmt::info!("Battery charge status:");
-        let battery_charge_status = self.battery_charge_status()?;
-        defmt::info!("{}", battery_charge_status);
+    /// Always bias the thermistor (Config.FTHRM), for fast battery removal detection
+    pub fn set_thermistor_bias_forced(&mut self, forced: bool) -> Result<(), Error<E>> {
+        self.modify(|config: Config| config.with_fthrm(forced))
+    }
 
-        Ok(())
+    /// Shut the device down after the ShdnTimer timeout (Config.SHDN)
+    pub fn shutdown(&mut self) -> Result<(), Error<E>> {
+        self.modify(|config: Config| config.with_shdn(true))
+    }
+
+    /// Shut down when SDA and SCL are held low, waking on bus activity (Config.COMMSH)
+    pub fn set_communication_shutdown(&mut self, enabled: bool) -> Result<(), Error<E>> {
+        self.modify(|config: Config| config.with_commsh(enabled))
+    }
+
+    /// Shut down when the battery is removed, as detected on the TH pin (Config.THSH)
+    pub fn set_th_shutdown(&mut self, enabled: bool) -> Result<(), Error<E>> {
+        self.modify(|config: Config| config.with_thsh(enabled))
+    }
+
+    /// Enable or disable the 1% SOC change alert, Status.dSOCi (Config2.dSOCen)
+    pub fn set_soc_change_alert(&mut self, enabled: bool) -> Result<(), Error<E>> {
+        self.modify(|config_2: Config2| config_2.with_d_so_cen(enabled))
+    }
+
+    /// Enable or disable temperature based alerts (Config2.TAlrtEn)
+    pub fn set_temperature_alert(&mut self, enabled: bool) -> Result<(), Error<E>> {
+        self.modify(|config_2: Config2| config_2.with_t_alrt_en(enabled))
+    }
+
+    /// Enable or disable the AtRate calculations (Config2.AtRateEn)
+    pub fn set_at_rate(&mut self, enabled: bool) -> Result<(), Error<E>> {
+        self.modify(|config_2: Config2| config_2.with_at_rate_en(enabled))
+    }
+
+    /// Enable or disable the dynamic power calculation (Config2.DPEn)
+    pub fn set_dynamic_power(&mut self, enabled: bool) -> Result<(), Error<E>> {
+        self.modify(|config_2: Config2| config_2.with_dp_en(enabled))
+    }
+
+    /// Restart the firmware without a full reset of the IC (Config2.POR_CMD). The gauge comes back
+    /// with Status.POR set and has to be configured again.
+    pub fn firmware_restart(&mut self) -> Result<(), Error<E>> {
+        self.modify(|config_2: Config2| config_2.with_por_cmd(true))
+    }
+
+    /// Get the VAlrtTh register (01h)
+    pub fn v_alrt_th_register(&mut self) -> Result<VAlrtTh, Error<E>> {
+        self.read_register_as_bitfield::<VAlrtTh>()
+    }
+
+    /// Get the TAlrtTh register (02h)
+    pub fn t_alrt_th_register(&mut self) -> Result<TAlrtTh, Error<E>> {
+        self.read_register_as_bitfield::<TAlrtTh>()
+    }
+
+    /// Get the SAlrtTh register (03h)
+    pub fn s_alrt_th_register(&mut self) -> Result<SAlrtTh, Error<E>> {
+        self.read_register_as_bitfield::<SAlrtTh>()
+    }
+
+    /// Get the IAlrtTh register (B4h)
+    pub fn i_alrt_th_register(&mut self) -> Result<IAlrtTh, Error<E>> {
+        self.read_register_as_bitfield::<IAlrtTh>()
+    }
+
+    /// Set the cell voltage alert thresholds in V (VAlrtTh), with 20mV resolution
+    pub fn set_voltage_alert_thresholds(
+        &mut self,
+        thresholds: AlertThresholds<Voltage<R>>,
+    ) -> Result<(), Error<E>> {
+        let to_threshold =
+            |volts| unsigned_threshold(self.register_resolver.voltage_to_register(volts));
+        let v_alrt_th = VAlrtTh::new()
+            .with_min(thresholds.min.map_or(VAlrtTh::DISABLED_MIN, to_threshold))
+            .with_max(thresholds.max.map_or(VAlrtTh::DISABLED_MAX, to_threshold));
+        self.write_bitfield_to_register(v_alrt_th)
+    }
+
+    /// Get the cell voltage alert thresholds in V (VAlrtTh)
+    pub fn voltage_alert_thresholds(&mut self) -> Result<AlertThresholds<Voltage<R>>, Error<E>> {
+        let v_alrt_th = self.v_alrt_th_register()?;
+        Ok(AlertThresholds::from_bytes(
+            v_alrt_th.min(),
+            v_alrt_th.max(),
+            (VAlrtTh::DISABLED_MIN, VAlrtTh::DISABLED_MAX),
+            |register| self.register_resolver.register_to_voltage(register),
+        ))
+    }
+
+    /// Set the temperature alert thresholds in degrees C (TAlrtTh), with 1°C resolution
+    pub fn set_temperature_alert_thresholds(
+        &mut self,
+        thresholds: AlertThresholds<Temperature<R>>,
+    ) -> Result<(), Error<E>> {
+        let to_threshold =
+            |celsius| signed_threshold(self.register_resolver.temperature_to_register(celsius));
+        let t_alrt_th = TAlrtTh::new()
+            .with_min(thresholds.min.map_or(TAlrtTh::DISABLED_MIN, to_threshold))
+            .with_max(thresholds.max.map_or(TAlrtTh::DISABLED_MAX, to_threshold));
+        self.write_bitfield_to_register(t_alrt_th)
+    }
+
+    /// Get the temperature alert thresholds in degrees C (TAlrtTh)
+    pub fn temperature_alert_thresholds(
+        &mut self,
+    ) -> Result<AlertThresholds<Temperature<R>>, Error<E>> {
+        let t_alrt_th = self.t_alrt_th_register()?;
+        Ok(AlertThresholds::from_bytes(
+            t_alrt_th.min(),
+            t_alrt_th.max(),
+            (TAlrtTh::DISABLED_MIN, TAlrtTh::DISABLED_MAX),
+            |register| self.register_resolver.register_to_temperature(register),
+        ))
+    }
+
+    /// Set the state of charge alert thresholds as a percentage (SAlrtTh), with 1% resolution
+    pub fn set_state_of_charge_alert_thresholds(
+        &mut self,
+        thresholds: AlertThresholds<Percentage<R>>,
+    ) -> Result<(), Error<E>> {
+        let to_threshold = |percentage| {
+            unsigned_threshold(self.register_resolver.percentage_to_register(percentage))
+        };
+        let s_alrt_th = SAlrtTh::new()
+            .with_min(thresholds.min.map_or(SAlrtTh::DISABLED_MIN, to_threshold))
+            .with_max(thresholds.max.map_or(SAlrtTh::DISABLED_MAX, to_threshold));
+        self.write_bitfield_to_register(s_alrt_th)
+    }
+
+    /// Get the state of charge alert thresholds as a percentage (SAlrtTh)
+    pub fn state_of_charge_alert_thresholds(
+        &mut self,
+    ) -> Result<AlertThresholds<Percentage<R>>, Error<E>> {
+        let s_alrt_th = self.s_alrt_th_register()?;
+        Ok(AlertThresholds::from_bytes(
+            s_alrt_th.min(),
+            s_alrt_th.max(),
+            (SAlrtTh::DISABLED_MIN, SAlrtTh::DISABLED_MAX),
+            |register| self.register_resolver.register_to_percentage(register),
+        ))
+    }
+
+    /// Set the current alert thresholds in A (IAlrtTh), with 400μV/RSENSE resolution
+    pub fn set_current_alert_thresholds(
+        &mut self,
+        thresholds: AlertThresholds<Current<R>>,
+    ) -> Result<(), Error<E>> {
+        let to_threshold =
+            |amps| signed_threshold(self.register_resolver.current_to_register(amps));
+        let i_alrt_th = IAlrtTh::new()
+            .with_min(thresholds.min.map_or(IAlrtTh::DISABLED_MIN, to_threshold))
+            .with_max(thresholds.max.map_or(IAlrtTh::DISABLED_MAX, to_threshold));
+        self.write_bitfield_to_register(i_alrt_th)
+    }
+
+    /// Get the current alert thresholds in A (IAlrtTh)
+    pub fn current_alert_thresholds(&mut self) -> Result<AlertThresholds<Current<R>>, Error<E>> {
+        let i_alrt_th = self.i_alrt_th_register()?;
+        Ok(AlertThresholds::from_bytes(
+            i_alrt_th.min(),
+            i_alrt_th.max(),
+            (IAlrtTh::DISABLED_MIN, IAlrtTh::DISABLED_MAX),
+            |register| self.register_resolver.register_to_current(register),
+        ))
+    }
+
+    /// Get MaxMinVolt Register (1Bh)
+    pub fn max_min_volt_register(&mut self) -> Result<MaxMinVolt, Error<E>> {
+        self.read_register_as_bitfield::<MaxMinVolt>()
+    }
+
+    /// Get MaxMinCurr Register (1Ch)
+    pub fn max_min_curr_register(&mut self) -> Result<MaxMinCurr, Error<E>> {
+        self.read_register_as_bitfield::<MaxMinCurr>()
+    }
+
+    /// Get MaxMinTemp Register (1Ah)
+    pub fn max_min_temp_register(&mut self) -> Result<MaxMinTemp, Error<E>> {
+        self.read_register_as_bitfield::<MaxMinTemp>()
+    }
+
+    /// Lowest and highest cell voltage in V since the last reset (MaxMinVolt), with 20mV
+    /// resolution. `None` if nothing has been logged yet.
+    pub fn voltage_max_min(&mut self) -> Result<Option<MaxMin<Voltage<R>>>, Error<E>> {
+        let max_min_volt = self.max_min_volt_register()?;
+        Ok(MaxMin::from_bytes(
+            max_min_volt.min(),
+            max_min_volt.max(),
+            (MaxMinVolt::RESET_MIN, MaxMinVolt::RESET_MAX),
+            |register| self.register_resolver.register_to_voltage(register),
+        ))
+    }
+
+    /// Lowest and highest current in A since the last reset (MaxMinCurr), with 400μV/RSENSE
+    /// resolution. `None` if nothing has been logged yet.
+    pub fn current_max_min(&mut self) -> Result<Option<MaxMin<Current<R>>>, Error<E>> {
+        let max_min_curr = self.max_min_curr_register()?;
+        Ok(MaxMin::from_bytes(
+            max_min_curr.min(),
+            max_min_curr.max(),
+            (MaxMinCurr::RESET_MIN, MaxMinCurr::RESET_MAX),
+            |register| self.register_resolver.register_to_current(register),
+        ))
+    }
+
+    /// Lowest and highest temperature in degrees C since the last reset (MaxMinTemp), with 1°C
+    /// resolution. `None` if nothing has been logged yet.
+    pub fn temperature_max_min(&mut self) -> Result<Option<MaxMin<Temperature<R>>>, Error<E>> {
+        let max_min_temp = self.max_min_temp_register()?;
+        Ok(MaxMin::from_bytes(
+            max_min_temp.min(),
+            max_min_temp.max(),
+            (MaxMinTemp::RESET_MIN, MaxMinTemp::RESET_MAX),
+            |register| self.register_resolver.register_to_temperature(register),
+        ))
+    }
+
+    /// Reset the MaxMinVolt, MaxMinCurr and MaxMinTemp registers, e.g. after they have been read
+    /// and logged. The gauge starts tracking again from the next reading.
+    pub fn reset_max_min(&mut self) -> Result<(), Error<E>> {
+        self.write_bitfield_to_register(MaxMinVolt::reset())?;
+        self.write_bitfield_to_register(MaxMinCurr::reset())?;
+        self.write_bitfield_to_register(MaxMinTemp::reset())
+    }
+
+    /// Find out why the ALRT pin was asserted and clear the cause.
+    ///
+    /// Reads the Status register and clears the alert flags it reported. Status is read again
+    /// just before the clear and only the reported flags are written as 0, so alerts raised in
+    /// between stay set and assert the pin again.
+    ///
+    /// `AlertEvent::PowerOnReset` is reported but not cleared: POR stays set until the gauge has
+    /// been configured (see `ez_config`), so it can't be lost by handling an unrelated alert.
+    ///
+    /// Status2 (B0h) is not read: its Hib and FullDet flags are states that never assert ALRT, so
+    /// they can't be why the pin fired. Read them with `status_2_register` when needed.
+    pub fn handle_alert(&mut self) -> Result<AlertEvents, Error<E>> {
+        let events = AlertEvents::from_status(self.status_register()?);
+        let clear = events.status() - Status::POR;
+        if !clear.is_empty() {
+            let status = self.read_register_as_u16(Register::STATUS)?;
+            self.write_register(Register::STATUS, status & !clear.bits())?;
+        }
+        Ok(events)
+    }
+
+    /// Handle an alert if the ALRT pin is asserted. ALRT is an active-low, open-drain output,
+    /// so `pin` needs a pull-up. Returns no events if the pin is not asserted.
+    pub fn poll_alert<P>(&mut self, pin: &mut P) -> Result<AlertEvents, Error<E>>
+    where
+        P: InputPin,
+    {
+        let asserted = pin
+            .is_low()
+            .map_err(|err| Error::Pin(digital::Error::kind(&err)))?;
+        if asserted {
+            self.handle_alert()
+        } else {
+            Ok(AlertEvents::empty())
+        }
+    }
+
+    /// Get the battery charge status
+    pub fn battery_charge_status(&mut self) -> Result<BatteryChargeStatus<R::Units>, Error<E>> {
+        let raw = RawChargeStatus {
+            rep_cap: self.read_register_as_u16(OutputRegister::REP_CAP)?,
+            rep_soc: self.read_register_as_u16(OutputRegister::REP_SOC)?,
+            full_cap_rep: self.read_register_as_u16(Register::FULL_CAP_REP)?,
+            tte: self.read_register_as_u16(OutputRegister::TTE)?,
+            ttf: self.read_register_as_u16(Register::TTF)?,
+            avg_current: self.read_register_as_u16(Register::AVG_CURRENT)?,
+            f_stat: self.read_register_as_u16(Register::F_STAT)?,
+        };
+        Ok(raw.resolve(&self.register_resolver))
+    }
+
+    /// Number of full charge/discharge cycles, in 16% steps (Cycles)
+    pub fn cycles(&mut self) -> Result<Cycles<R>, Error<E>> {
+        let register = self.read_register_as_u16(Register::CYCLES)?;
+        Ok(self.register_resolver.register_to_cycles(register))
+    }
+
+    /// Present capacity compared to DesignCap (Age)
+    pub fn age(&mut self) -> Result<Percentage<R>, Error<E>> {
+        let register = self.read_register_as_u16(Register::AGE)?;
+        Ok(self.register_resolver.register_to_percentage(register))
+    }
+
+    /// Expected capacity of the cell (DesignCap)
+    pub fn design_cap(&mut self) -> Result<Capacity<R>, Error<E>> {
+        let register = self.read_register_as_u16(Register::DESIGN_CAP)?;
+        Ok(self.register_resolver.register_to_capacity(register))
+    }
+
+    /// Present full capacity of the cell, without temperature and empty compensation
+    /// (FullCapNom)
+    pub fn full_cap_nom(&mut self) -> Result<Capacity<R>, Error<E>> {
+        let register = self.read_register_as_u16(Register::FULL_CAP_NOM)?;
+        Ok(self.register_resolver.register_to_capacity(register))
+    }
+
+    /// Cycle count, capacity fade and, on the MAX17263, the forecast remaining cycle life
+    pub fn state_of_health(&mut self) -> Result<StateOfHealth<R::Units>, Error<E>> {
+        let cycles = self.read_register_as_u16(Register::CYCLES)?;
+        let age = self.read_register_as_u16(Register::AGE)?;
+        let design_cap = self.design_cap()?;
+        let full_cap_nom = self.full_cap_nom()?;
+        let age_forecast = match M::AGE_FORECAST {
+            Some(register) => Some(self.read_register_as_u16(register)?),
+            None => None,
+        };
+        let resolver = &self.register_resolver;
+        Ok(StateOfHealth {
+            cycles: resolver.register_to_cycles(cycles),
+            design_cap,
+            full_cap_nom,
+            age: resolver.register_to_percentage(age),
+            capacity_fade: resolver.register_to_percentage(AGE_FULL.saturating_sub(age)),
+            remaining_cycles: age_forecast
+                .filter(|&forecast| forecast != 0)
+                .map(|forecast| resolver.register_to_cycles(forecast.saturating_sub(cycles))),
+        })
+    }
+
+    /// Read the learned parameters (step 3.5), to be saved to non-volatile memory, e.g. with
+    /// `LearnedParameters::to_bytes`
+    pub fn save_learned_parameters(&mut self) -> Result<LearnedParameters, Error<E>> {
+        Ok(LearnedParameters {
+            rcomp0: self.read_register_as_u16(Register::R_COMP_0)?,
+            temp_co: self.read_register_as_u16(Register::TEMP_CO)?,
+            full_cap_rep: self.read_register_as_u16(Register::FULL_CAP_REP)?,
+            cycles: self.read_register_as_u16(Register::CYCLES)?,
+            full_cap_nom: self.read_register_as_u16(Register::FULL_CAP_NOM)?,
+            qr_table: Some([
+                self.read_register_as_u16(Register::QR_TABLE_00)?,
+                self.read_register_as_u16(Register::QR_TABLE_10)?,
+                self.read_register_as_u16(Register::QR_TABLE_20)?,
+                self.read_register_as_u16(Register::QR_TABLE_30)?,
+            ]),
+        })
+    }
+
+    /// Restore saved learned parameters after a POR (step 4). Call this once the gauge has been
+    /// configured, e.g. with `ez_config`. Every write is verified, and the sequence waits 700ms in
+    /// total for the gauge to process the restored values.
+    pub fn restore_learned_parameters<D>(
+        &mut self,
+        mut delay: D,
+        learned: &LearnedParameters,
+    ) -> Result<(), Error<E>>
+    where
+        D: DelayNs,
+    {
+        defmt::info!("Restoring learned parameters: {}", learned);
+        // Step 4.1: restore RComp0, TempCo, FullCapNom and the QRTable values
+        for (register, data) in learned.characterization_writes() {
+            self.write_and_verify_register(register, data, &mut delay)?;
+        }
+        // Step 4.2: wait 350ms
+        delay.delay_ms(RESTORE_DELAY_MS);
+        // Step 4.3: restore FullCap, from the present MixSOC
+        let mix_soc = self.read_register_as_u16(Register::MIX_SOC)?;
+        for (register, data) in learned.capacity_writes(mix_soc) {
+            self.write_and_verify_register(register, data, &mut delay)?;
+        }
+        // Step 4.4: wait 350ms
+        delay.delay_ms(RESTORE_DELAY_MS);
+        // Step 4.5: restore Cycles
+        self.write_and_verify_register(Register::CYCLES, learned.cycles, &mut delay)
+    }
+
+    /// Setup the fuel gauge as per:
+    /// ModelGauge m5 Host Side Software Implementation Guide UG6595; Rev 4; 12/21
+    /// https://www.analog.com/media/en/technical-documentation/user-guides/modelgauge-m5-host-side-software-implementation-guide.pdf
+    /// page 6
+    ///
+    /// The polling loops give up with `Error::Timeout` after the limits set with `set_timeouts`.
+    pub fn ez_config<D>(&mut self, delay: D, ez_config: EzConfig<R::Units>) -> Result<(), Error<E>>
+    where
+        D: DelayNs,
+    {
+        defmt::info!(
+            "Starting MAX1726x EZ Config with configuration: {}",
+            ez_config
+        );
+        self.initialise(
+            delay,
+            |gauge, _delay| gauge.write_ez_config(&ez_config),
+            |_gauge| Ok(()),
+        )
+    }
+
+    /// 2.1 OPTION 1 EZ Config (No INI file is needed)
+    fn write_ez_config(&mut self, ez_config: &EzConfig<R::Units>) -> Result<(), Error<E>> {
+        defmt::info!("Option 1 EZ Config");
+        defmt::info!("Writing DESIGN_CAP, I_CHG_TERM, V_EMPTY, ModelCFG");
+        for (register, data) in ez_config.register_writes(&self.register_resolver) {
+            self.write_register(register, data)?;
+        }
+        Ok(())
+    }
+
+    /// Setup the fuel gauge with a custom characterized model, as per "2.2 OPTION 2 Custom
+    /// Short INI" and "2.3 OPTION 3 Custom Full INI" of the implementation guide. See `ez_config`
+    /// for the rest of the sequence.
+    ///
+    /// If the model has a model table, model access is unlocked, the table is written and
+    /// verified, and model access is locked again. If the table still reads back after locking,
+    /// this fails with `Error::ModelNotLocked`.
+    ///
+    /// DesignCap, dQAcc, IChgTerm, VEmpty, dPAcc and the optional configuration registers are
+    /// written before ModelCfg.Refresh is set; RComp0, TempCo and the QRTable after it clears.
+    pub fn load_custom_model<D>(&mut self, delay: D, model: &CustomModel) -> Result<(), Error<E>>
+    where
+        D: DelayNs,
+    {
+        defmt::info!("Starting MAX1726x custom model load: {}", model);
+        self.initialise(
+            delay,
+            |gauge, delay| {
+                if let Some(model_table) = &model.model_table {
+                    defmt::info!("Option 3 Custom Full INI");
+                    gauge.write_model_table(model_table, delay)?;
+                } else {
+                    defmt::info!("Option 2 Custom Short INI");
+                }
+                defmt::info!("Writing model parameters");
+                for (register, data) in model.parameter_writes() {
+                    gauge.write_register(register, data)?;
+                }
+                gauge.write_register(Register::MODEL_CFG, model.model_cfg_refresh())
+            },
+            |gauge| {
+                defmt::info!("Writing RComp0, TempCo and QRTable");
+                for (register, data) in model.post_refresh_writes() {
+                    gauge.write_register(register, data)?;
+                }
+                Ok(())
+            },
+        )
+    }
+
+    /// Unlock model access, write and verify the model table, then lock model access again
+    fn write_model_table<D>(
+        &mut self,
+        model_table: &[u16; MODEL_TABLE_LEN],
+        delay: &mut D,
+    ) -> Result<(), Error<E>>
+    where
+        D: DelayNs,
+    {
+        defmt::info!("Unlocking model access");
+        for (register, data) in MODEL_UNLOCK {
+            self.write_register(register, data)?;
+        }
+        defmt::info!("Writing model table");
+        for (register, &data) in (Register::MODEL_TABLE..).zip(model_table) {
+            self.write_and_verify_register(register, data, &mut *delay)?;
+        }
+        defmt::info!("Locking model access");
+        let mut attempt: u8 = 0;
+        loop {
+            for (register, data) in MODEL_LOCK {
+                self.write_register(register, data)?;
+            }
+            if self.model_table_locked()? {
+                return Ok(());
+            }
+            attempt += 1;
+            if attempt > WRITE_VERIFY_RETRIES {
+                return Err(Error::ModelNotLocked);
+            }
+        }
+    }
+
+    /// Whether model access is locked, i.e. the whole model table reads as zero
+    fn model_table_locked(&mut self) -> Result<bool, Error<E>> {
+        for register in (Register::MODEL_TABLE..).take(MODEL_TABLE_LEN) {
+            if self.read_register_as_u16(register)? != 0 {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+
+    /// The initialisation sequence shared by `ez_config` and `load_custom_model`. After a POR,
+    /// `load` writes the model in step 2 once the gauge is ready and out of hibernate, and
+    /// `refreshed` writes what must follow once ModelCFG.Refresh has cleared.
+    fn initialise<D, F, G>(&mut self, mut delay: D, load: F, refreshed: G) -> Result<(), Error<E>>
+    where
+        D: DelayNs,
+        F: FnOnce(&mut Self, &mut D) -> Result<(), Error<E>>,
+        G: FnOnce(&mut Self) -> Result<(), Error<E>>,
+    {
+        // Step 0: check for POR
+        defmt::info!("Checking for Power On Reset (POR)");
+        let status = self.status_register()?;
+        defmt::info!("Initial status: {}", status);
+        let status_por = status & Status::POR;
+        if status_por.is_empty() {
+            defmt::info!("Power On Reset (POR) not detected.");
+            // Go to step 3.2
+        } else {
+            // Do steps 1-2
+
+            // Step 1. Delay until FSTAT.DNR bit == 0
+            self.wait_for_data_ready(&mut delay)?;
+
+            defmt::info!("DNR bit cleared. Proceeding to Step 2.");
+            // Step 2. Initialise configuration
+            let hib_cfg = self.exit_hibernate()?;
+
+            load(self, &mut delay)?;
+
+            // Poll ModelCFG.Refresh(highest bit),
+            // proceed to Step 3 when ModelCFG.Refresh=0.
+            self.wait_for_model_refresh(&mut delay)?;
+            refreshed(self)?;
+
+            // Restore Original HibCFG value
+            defmt::info!("Restoring Hibernate Mode");
+            self.write_bitfield_to_register(hib_cfg)?;
+
+            // Proceed to Step 3.
+        }
+        defmt::info!("Proceeding to Step 3.");
+        // Step 3: Initialization Complete
+        self.clear_por(delay)?;
+        defmt::info!("Initialization Complete");
+
+        defmt::info!("Battery charge status:");
+        let battery_charge_status = self.battery_charge_status()?;
+        defmt::info!("{}", battery_charge_status);
+
+        Ok(())
+    }
+
+    /// Step 1: wait until FStat.DNR clears
+    fn wait_for_data_ready<D>(&mut self, delay: &mut D) -> Result<(), Error<E>>
+    where
+        D: DelayNs,
+    {
+        defmt::info!("Delaying for DNR bit to clear...");
+        let mut timer = PollTimer::new(TimeoutStep::DataNotReady, self.timeouts.data_ready_ms);
+        while !(self.fstat_register()? & FStat::DNR).is_empty() {
+            // 10ms Wait Loop. Do not continue until FSTAT.DNR==0
+            delay.delay_ms(timer.tick()?);
+        }
+        Ok(())
+    }
+
+    /// Exit hibernate mode, returning the original HibCfg value to restore afterwards
+    fn exit_hibernate(&mut self) -> Result<HibCfg, Error<E>> {
+        // Store original HibCFG value
+        let hib_cfg = self.hib_cfg_register()?;
+        defmt::info!("HibCFG: {}", hib_cfg);
+        defmt::info!("Exiting Hibernate Mode");
+        for (register, data) in EXIT_HIBERNATE {
+            self.write_register(register, data)?;
+        }
+        Ok(hib_cfg)
+    }
+
+    /// Wait until ModelCfg.Refresh clears after the model has been written
+    fn wait_for_model_refresh<D>(&mut self, delay: &mut D) -> Result<(), Error<E>>
+    where
+        D: DelayNs,
+    {
+        defmt::info!("Waiting for ModelCFG.Refresh to clear...");
+        let mut timer = PollTimer::new(TimeoutStep::ModelRefresh, self.timeouts.model_refresh_ms);
+        while self.read_register_as_bitfield::<ModelCfg>()?.refresh() {
+            delay.delay_ms(timer.tick()?);
+        }
+        // do not continue until ModelCFG.Refresh==0
+        Ok(())
+    }
+
+    /// Step 3: clear the POR bit to indicate that the model and parameters are successfully
+    /// loaded
+    fn clear_por<D>(&mut self, delay: D) -> Result<(), Error<E>>
+    where
+        D: DelayNs,
+    {
+        defmt::info!("Clearing Power On Reset (POR) bit");
+        let status = status_without_por(self.read_register_as_u16(Register::STATUS)?);
+        // Write and Verify Status with POR bit Cleared
+        self.write_and_verify_register(Register::STATUS, status, delay)
+    }
+}
+
+/// Check a number of cells in series fits PackCfg.NCELLS
+fn check_cell_count<E>(cells: u8) -> Result<(), Error<E>>
+where
+    E: i2c::Error,
+{
+    if (1..=MAX_CELLS).contains(&cells) {
+        Ok(())
+    } else {
+        Err(Error::InvalidCellCount(cells))
+    }
+}
+
+/// Multi-cell packs (MAX17261, MAX17263). VCell reports the per-cell voltage, i.e. 2.5x the
+/// voltage at the Cellx pin, and Batt reports the total pack voltage.
+///
+/// Neither part balances cells or measures the individual cells of the stack, so there are no
+/// cell-balance registers to read. VCell is the average cell voltage; balancing is left to the
+/// charger or protection IC.
+///
+/// PackCfg is only reachable through these methods, so a single-cell part can't read it:
+///
+/// ```compile_fail
+/// # use embedded_hal::i2c::{ErrorKind, I2c};
+/// # use max1726x::registers::PackCfg;
+/// # fn pack<I2C: I2c<Error = ErrorKind>>(gauge: &mut max1726x::max17260::Max17260<I2C>) {
+/// let pack_cfg = gauge.read_register_as_bitfield::<PackCfg>();
+/// # }
+/// ```
+impl<M, I2C, E, R> Max1726x<M, I2C, R>
+where
+    M: MultiCell,
+    I2C: i2c::I2c<Error = E>,
+    E: i2c::Error,
+    R: RegisterResolver,
+{
+    /// Get PackCfg Register (BDh)
+    pub fn pack_cfg_register(&mut self) -> Result<PackCfg, Error<E>> {
+        Ok(PackCfg::from(self.read_register_as_u16(M::PACK_CFG)?))
+    }
+
+    /// Write and verify PackCfg. Fails with `Error::InvalidCellCount` without writing if NCELLS
+    /// selects more than `MAX_CELLS` cells. Call before `ez_config` or `load_custom_model`, so the model is loaded for the right
+    /// pack, or use `ez_config_with_pack_cfg`.
+    pub fn set_pack_cfg<D>(&mut self, delay: D, pack_cfg: PackCfg) -> Result<(), Error<E>>
+    where
+        D: DelayNs,
+    {
+        check_cell_count(pack_cfg.cells())?;
+        self.write_and_verify_register(M::PACK_CFG, pack_cfg.into(), delay)
+    }
+
+    /// Setup the fuel gauge as `ez_config`, writing PackCfg in step 2 before the EZ model.
+    /// Like the rest of the configuration, PackCfg is only written after a POR.
+    pub fn ez_config_with_pack_cfg<D>(
+        &mut self,
+        delay: D,
+        pack_cfg: PackCfg,
+        ez_config: EzConfig<R::Units>,
+    ) -> Result<(), Error<E>>
+    where
+        D: DelayNs,
+    {
+        check_cell_count(pack_cfg.cells())?;
+        defmt::info!("Starting MAX1726x EZ Config with PackCfg: {}", pack_cfg);
+        self.initialise(
+            delay,
+            |gauge, delay| {
+                defmt::info!("Writing PackCfg");
+                gauge.set_pack_cfg(&mut *delay, pack_cfg)?;
+                gauge.write_ez_config(&ez_config)
+            },
+            |_gauge| Ok(()),
+        )
+    }
+
+    /// Number of cells in series, from PackCfg.NCELLS (cells minus one)
+    pub fn cell_count(&mut self) -> Result<u8, Error<E>> {
+        Ok(self.pack_cfg_register()?.cells())
+    }
+
+    /// Set the number of cells in series (PackCfg.NCELLS), keeping the rest of PackCfg. Call
+    /// before `ez_config` or `load_custom_model`, so the model is loaded for the right pack.
+    pub fn set_cell_count<D>(&mut self, delay: D, cells: u8) -> Result<(), Error<E>>
+    where
+        D: DelayNs,
+    {
+        check_cell_count(cells)?;
+        let pack_cfg = self.pack_cfg_register()?.with_cells(cells);
+        self.set_pack_cfg(delay, pack_cfg)
+    }
+
+    /// Check that PackCfg is configured for a pack of `cells` cells in series
+    pub fn verify_cell_count(&mut self, cells: u8) -> Result<(), Error<E>> {
+        let configured = self.cell_count()?;
+        if configured == cells {
+            Ok(())
+        } else {
+            Err(Error::CellCountMismatch {
+                expected: cells,
+                configured,
+            })
+        }
+    }
+
+    /// Total pack voltage in V (Batt)
+    pub fn pack_voltage(&mut self) -> Result<Voltage<R>, Error<E>> {
+        let register = self.read_register_as_u16(M::BATT)?;
+        Ok(self.register_resolver.register_to_pack_voltage(register))
+    }
+
+    /// Per-cell voltage in V (VCell)
+    pub fn cell_voltage(&mut self) -> Result<Voltage<R>, Error<E>> {
+        self.battery_voltage()
+    }
+
+    /// Average per-cell voltage in V (AvgVCell)
+    pub fn average_cell_voltage(&mut self) -> Result<Voltage<R>, Error<E>> {
+        self.battery_average_voltage()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::alert::AlertEvent;
+    use crate::max17263::registers::{Max17263RegisterResolver, Register as Max17263};
+    use crate::simulator::{Max1726xSimulator, SimClock};
+    use crate::units::{Amps, Celsius, MilliAmpHours, Ohms, Percent, Volts};
+    use core::cell::RefCell;
+    use embedded_hal::i2c::I2c;
+    use embedded_hal_bus::i2c::RefCellDevice;
+
+    fn ez_config() -> EzConfig {
+        EzConfig {
+            charge_voltage: Volts(4.2),
+            design_cap: MilliAmpHours(2500.0),
+            i_chg_term: Amps(0.1),
+            v_empty: VEmpty::init(3300, 3880),
+        }
+    }
+
+    fn gauge(
+        simulator: Max1726xSimulator<'_>,
+    ) -> Max1726x<Max17263, Max1726xSimulator<'_>, Max17263RegisterResolver> {
+        Max1726x::new(simulator, Max17263RegisterResolver::new(Ohms(0.010)))
+    }
+
+    #[test]
+    fn ez_config_after_por() {
+        let clock = SimClock::new();
+        let mut gauge = gauge(Max1726xSimulator::new(&clock));
+        gauge.ez_config(clock.delay(), ez_config()).unwrap();
+        let simulator = gauge.destroy();
+        // 2500mAh and 100mA with a 10mΩ sense resistor
+        assert_eq!(simulator.register(Register::DESIGN_CAP), 5000);
+        assert_eq!(simulator.register(Register::I_CHG_TERM), 640);
+        assert_eq!(simulator.register(Register::V_EMPTY), 0xA561);
+        assert_eq!(simulator.register(Register::MODEL_CFG), 0x0000);
+        assert_eq!(simulator.register(Register::HIB_CFG), 0x870C);
+        assert_eq!(simulator.register(Register::STATUS) & Status::POR.bits(), 0);
+        assert!(!simulator.is_hibernating());
+        // DNR was waited out before configuring
+        assert!(clock.now_us() >= 710_000);
+    }
+
+    fn custom_model() -> CustomModel {
+        let mut model_table = [0u16; MODEL_TABLE_LEN];
+        for (i, word) in model_table.iter_mut().enumerate() {
+            *word = 0x9000 + i as u16;
+        }
+        CustomModel {
+            model_table: Some(model_table),
+            design_cap: 0x1194,
+            i_chg_term: 0x0333,
+            v_empty: VEmpty::init(2500, 3000),
+            model_cfg: ModelCfg::new().with_model_id(6),
+            rcomp0: 0x0035,
+            temp_co: 0x1A2B,
+            qr_table: [0x3A00, 0x1900, 0x0C80, 0x0880],
+            learn_cfg: Some(0x4486),
+            filter_cfg: None,
+            relax_cfg: Some(0x083B),
+            misc_cfg: None,
+            full_soc_thr: Some(0x5F05),
+        }
+    }
+
+    #[test]
+    fn load_custom_full_model() {
+        let clock = SimClock::new();
+        let mut gauge = gauge(Max1726xSimulator::new(&clock));
+        let model = custom_model();
+        gauge.load_custom_model(clock.delay(), &model).unwrap();
+        let simulator = gauge.destroy();
+        for (i, &word) in model.model_table.unwrap().iter().enumerate() {
+            assert_eq!(simulator.register(Register::MODEL_TABLE + i as u8), word);
+        }
+        assert_eq!(simulator.register(Register::MODEL_UNLOCK_1), 0);
+        assert_eq!(simulator.register(Register::MODEL_UNLOCK_2), 0);
+        for (register, data) in [
+            (Register::DESIGN_CAP, 0x1194),
+            // DesignCap / 32 and dQAcc x 44138 / DesignCap
+            (Register::D_Q_ACC, 0x008C),
+            (Register::D_P_ACC, 0x055D),
+            (Register::I_CHG_TERM, 0x0333),
+            (Register::R_COMP_0, 0x0035),
+            (Register::TEMP_CO, 0x1A2B),
+            (Register::QR_TABLE_30, 0x0880),
+            (Register::LEARN_CFG, 0x4486),
+            (Register::RELAX_CFG, 0x083B),
+            (Register::FULL_SOC_THR, 0x5F05),
+            // Refresh has been waited out
+            (Register::MODEL_CFG, 0x0060),
+            (Register::HIB_CFG, 0x870C),
+        ] {
+            assert_eq!(simulator.register(register), data);
+        }
+        assert_eq!(simulator.register(Register::FILTER_CFG), 0);
+        assert_eq!(simulator.register(Register::STATUS) & Status::POR.bits(), 0);
+    }
+
+    #[test]
+    fn load_custom_short_model() {
+        let clock = SimClock::new();
+        let mut gauge = gauge(Max1726xSimulator::new(&clock));
+        let model = CustomModel {
+            model_table: None,
+            ..custom_model()
+        };
+        gauge.load_custom_model(clock.delay(), &model).unwrap();
+        let simulator = gauge.destroy();
+        assert_eq!(simulator.register(Register::MODEL_TABLE), 0);
+        assert_eq!(simulator.register(Register::R_COMP_0), 0x0035);
+        assert_eq!(simulator.register(Register::MODEL_CFG), 0x0060);
+    }
+
+    /// Records every register write made through it, with whether a model refresh was pending
+    /// after the write
+    struct RecordingBus<'a> {
+        simulator: Max1726xSimulator<'a>,
+        writes: Vec<(u8, u16, bool)>,
+    }
+
+    impl i2c::ErrorType for RecordingBus<'_> {
+        type Error = i2c::ErrorKind;
+    }
+
+    impl I2c for RecordingBus<'_> {
+        fn transaction(
+            &mut self,
+            address: u8,
+            operations: &mut [i2c::Operation<'_>],
+        ) -> Result<(), Self::Error> {
+            self.simulator.transaction(address, operations)?;
+            let refreshing = ModelCfg::from(self.simulator.register(Register::MODEL_CFG)).refresh();
+            for operation in operations.iter() {
+                if let i2c::Operation::Write([register, low, high, ..]) = operation {
+                    let data = u16::from_le_bytes([*low, *high]);
+                    self.writes.push((*register, data, refreshing));
+                }
+            }
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn load_custom_model_write_order() {
+        let clock = SimClock::new();
+        let bus = RecordingBus {
+            simulator: Max1726xSimulator::new(&clock),
+            writes: Vec::new(),
+        };
+        let mut gauge =
+            Max1726x::<Max17263, _, _>::new(bus, Max17263RegisterResolver::new(Ohms(0.010)));
+        let model = CustomModel {
+            model_table: None,
+            ..custom_model()
+        };
+        gauge.load_custom_model(clock.delay(), &model).unwrap();
+        let writes = gauge.destroy().writes;
+        let position = |register| {
+            writes
+                .iter()
+                .position(|&(written, _, _)| written == register)
+                .unwrap()
+        };
+        let model_cfg = position(Register::MODEL_CFG);
+        for register in [
+            Register::DESIGN_CAP,
+            Register::D_Q_ACC,
+            Register::I_CHG_TERM,
+            Register::V_EMPTY,
+            Register::D_P_ACC,
+        ] {
+            assert!(position(register) < model_cfg);
+        }
+        assert!(position(Register::D_Q_ACC) < position(Register::D_P_ACC));
+        assert!(writes[model_cfg].2);
+        // RComp0, TempCo and the QRTable follow the cleared refresh
+        for register in [
+            Register::R_COMP_0,
+            Register::TEMP_CO,
+            Register::QR_TABLE_00,
+            Register::QR_TABLE_10,
+            Register::QR_TABLE_20,
+            Register::QR_TABLE_30,
+        ] {
+            let write = position(register);
+            assert!(write > model_cfg);
+            assert!(!writes[write].2);
+        }
+    }
+
+    #[test]
+    fn ez_config_without_por_keeps_configuration() {
+        let clock = SimClock::new();
+        let mut simulator = Max1726xSimulator::new(&clock);
+        simulator.set_register(Register::STATUS, 0x0000);
+        let mut gauge = gauge(simulator);
+        gauge.ez_config(clock.delay(), ez_config()).unwrap();
+        let simulator = gauge.destroy();
+        assert_eq!(simulator.register(Register::DESIGN_CAP), 0x0BB8);
+        assert!(simulator.is_hibernating());
+    }
+
+    #[test]
+    fn ez_config_high_charge_voltage() {
+        let clock = SimClock::new();
+        let mut gauge = gauge(Max1726xSimulator::new(&clock));
+        let ez_config = EzConfig {
+            charge_voltage: Volts(4.4),
+            ..ez_config()
+        };
+        gauge.ez_config(clock.delay(), ez_config).unwrap();
+        let model_cfg = gauge.read_register_as_bitfield::<ModelCfg>().unwrap();
+        assert!(model_cfg.v_chg());
+        assert!(!model_cfg.refresh());
+    }
+
+    #[test]
+    fn data_not_ready_times_out() {
+        let clock = SimClock::new();
+        let mut simulator = Max1726xSimulator::new(&clock);
+        simulator.set_data_ready_time_ms(None);
+        let mut gauge = gauge(simulator);
+        gauge.set_timeouts(Timeouts {
+            data_ready_ms: 100,
+            ..Timeouts::default()
+        });
+        let result = gauge.ez_config(clock.delay(), ez_config());
+        assert!(matches!(
+            result,
+            Err(Error::Timeout(TimeoutStep::DataNotReady))
+        ));
+        assert_eq!(clock.now_us(), 100_000);
+    }
+
+    #[test]
+    fn poll_timer_saturates() {
+        let mut timer = PollTimer::new(TimeoutStep::DataNotReady, u32::MAX);
+        timer.waited_ms = u32::MAX - 5;
+        assert_eq!(timer.tick::<i2c::ErrorKind>().unwrap(), POLL_INTERVAL_MS);
+        assert!(matches!(
+            timer.tick::<i2c::ErrorKind>(),
+            Err(Error::Timeout(TimeoutStep::DataNotReady))
+        ));
+    }
+
+    #[test]
+    fn model_refresh_times_out() {
+        let clock = SimClock::new();
+        let mut simulator = Max1726xSimulator::new(&clock);
+        simulator.set_model_refresh_time_ms(None);
+        let mut gauge = gauge(simulator);
+        gauge.set_timeouts(Timeouts {
+            model_refresh_ms: 50,
+            ..Timeouts::default()
+        });
+        let result = gauge.ez_config(clock.delay(), ez_config());
+        assert!(matches!(
+            result,
+            Err(Error::Timeout(TimeoutStep::ModelRefresh))
+        ));
+        assert_eq!(clock.now_us(), 710_000 + 50_000);
+    }
+
+    #[test]
+    fn disconnected_gauge_reports_i2c_error() {
+        let clock = SimClock::new();
+        let mut simulator = Max1726xSimulator::new(&clock);
+        simulator.set_connected(false);
+        let mut gauge = gauge(simulator);
+        assert!(matches!(
+            gauge.ez_config(clock.delay(), ez_config()),
+            Err(Error::I2c(_))
+        ));
+    }
+
+    #[test]
+    fn write_and_verify_register() {
+        let clock = SimClock::new();
+        let mut gauge = gauge(Max1726xSimulator::new(&clock));
+        gauge
+            .write_and_verify_register(Register::DESIGN_CAP, 0x1234, clock.delay())
+            .unwrap();
+        assert_eq!(
+            gauge.read_register_as_u16(Register::DESIGN_CAP).unwrap(),
+            0x1234
+        );
+        // Read-only registers never take the written value
+        assert!(matches!(
+            gauge.write_and_verify_register(OutputRegister::REP_SOC, 0, clock.delay()),
+            Err(Error::WriteNotVerified {
+                register: OutputRegister::REP_SOC,
+                write: 0,
+                read: 0x3200
+            })
+        ));
+    }
+
+    #[test]
+    fn measurements() {
+        let clock = SimClock::new();
+        let mut gauge = gauge(Max1726xSimulator::new(&clock));
+        assert!((gauge.battery_voltage().unwrap().0 - 3.7).abs() < 1e-3);
+        assert_eq!(gauge.battery_current().unwrap(), Amps(0.0));
+        assert_eq!(gauge.battery_temperature().unwrap(), Celsius(25.0));
+        assert_eq!(gauge.battery_state_of_charge().unwrap(), Percent(50.0));
+    }
+
+    #[test]
+    fn config_options() {
+        let clock = SimClock::new();
+        let mut gauge = gauge(Max1726xSimulator::new(&clock));
+        assert_eq!(gauge.temperature_source().unwrap(), TemperatureSource::Die);
+        gauge
+            .set_temperature_source(TemperatureSource::Thermistor)
+            .unwrap();
+        gauge.set_alerts_enabled(true).unwrap();
+        gauge
+            .set_sticky_alerts(StickyAlerts::VOLTAGE | StickyAlerts::CURRENT)
+            .unwrap();
+        let config = gauge.config_register().unwrap();
+        assert_eq!(config.temperature_source(), TemperatureSource::Thermistor);
+        assert!(config.aen());
+        assert_eq!(
+            config.sticky_alerts(),
+            StickyAlerts::VOLTAGE | StickyAlerts::CURRENT
+        );
+        assert!(config.ten());
+
+        gauge.set_soc_change_alert(true).unwrap();
+        gauge.set_temperature_alert(false).unwrap();
+        let config_2 = gauge.config_2_register().unwrap();
+        assert!(config_2.d_so_cen());
+        assert!(!config_2.t_alrt_en());
+        assert!(config_2.at_rate_en());
+    }
+
+    #[test]
+    fn alert_thresholds() {
+        let clock = SimClock::new();
+        let mut gauge = gauge(Max1726xSimulator::new(&clock));
+        assert_eq!(
+            gauge.voltage_alert_thresholds().unwrap(),
+            AlertThresholds::DISABLED
+        );
+        assert_eq!(
+            gauge.current_alert_thresholds().unwrap(),
+            AlertThresholds::DISABLED
+        );
+
+        gauge
+            .set_voltage_alert_thresholds(AlertThresholds {
+                min: Some(Volts(3.0)),
+                max: Some(Volts(4.2)),
+            })
+            .unwrap();
+        gauge
+            .set_temperature_alert_thresholds(AlertThresholds {
+                min: None,
+                max: Some(Celsius(60.0)),
+            })
+            .unwrap();
+        gauge
+            .set_state_of_charge_alert_thresholds(AlertThresholds {
+                min: Some(Percent(5.0)),
+                max: None,
+            })
+            .unwrap();
+        // 40mA resolution with a 10mΩ sense resistor
+        gauge
+            .set_current_alert_thresholds(AlertThresholds {
+                min: Some(Amps(-2.0)),
+                max: Some(Amps(2.01)),
+            })
+            .unwrap();
+
+        assert_eq!(u16::from(gauge.v_alrt_th_register().unwrap()), 0xD296);
+        assert_eq!(u16::from(gauge.t_alrt_th_register().unwrap()), 0x3C80);
+        assert_eq!(u16::from(gauge.s_alrt_th_register().unwrap()), 0xFF05);
+        assert_eq!(u16::from(gauge.i_alrt_th_register().unwrap()), 0x32CE);
+
+        let voltage = gauge.voltage_alert_thresholds().unwrap();
+        assert!((voltage.min.unwrap().0 - 3.0).abs() < 1e-9);
+        assert!((voltage.max.unwrap().0 - 4.2).abs() < 1e-9);
+        assert_eq!(
+            gauge.temperature_alert_thresholds().unwrap(),
+            AlertThresholds {
+                min: None,
+                max: Some(Celsius(60.0))
+            }
+        );
+        assert_eq!(
+            gauge.state_of_charge_alert_thresholds().unwrap(),
+            AlertThresholds {
+                min: Some(Percent(5.0)),
+                max: None
+            }
+        );
+        let current = gauge.current_alert_thresholds().unwrap();
+        assert!((current.min.unwrap().0 + 2.0).abs() < 1e-9);
+        assert!((current.max.unwrap().0 - 2.0).abs() < 1e-9);
+
+        // Out of range limits saturate instead of wrapping
+        gauge
+            .set_temperature_alert_thresholds(AlertThresholds {
+                min: Some(Celsius(-300.0)),
+                max: Some(Celsius(300.0)),
+            })
+            .unwrap();
+        assert_eq!(u16::from(gauge.t_alrt_th_register().unwrap()), 0x7F80);
+    }
+
+    /// ALRT pin driven by the test
+    struct AlertPin(bool);
+
+    impl digital::ErrorType for AlertPin {
+        type Error = core::convert::Infallible;
+    }
+
+    impl InputPin for AlertPin {
+        fn is_high(&mut self) -> Result<bool, Self::Error> {
+            Ok(!self.0)
+        }
+
+        fn is_low(&mut self) -> Result<bool, Self::Error> {
+            Ok(self.0)
+        }
+    }
+
+    /// Counts the bus transactions made through it
+    struct CountingBus<'a> {
+        simulator: Max1726xSimulator<'a>,
+        transactions: usize,
+    }
+
+    impl i2c::ErrorType for CountingBus<'_> {
+        type Error = i2c::ErrorKind;
+    }
+
+    impl I2c for CountingBus<'_> {
+        fn transaction(
+            &mut self,
+            address: u8,
+            operations: &mut [i2c::Operation<'_>],
+        ) -> Result<(), Self::Error> {
+            self.transactions += 1;
+            self.simulator.transaction(address, operations)
+        }
+    }
+
+    #[test]
+    fn snapshot() {
+        let clock = SimClock::new();
+        let mut simulator = Max1726xSimulator::new(&clock);
+        simulator.set_register(Register::CYCLES, 0x0123);
+        simulator.set_register(Register::AVG_CURRENT, 0xF380);
+        let bus = CountingBus {
+            simulator,
+            transactions: 0,
+        };
+        let mut gauge =
+            Max1726x::<Max17263, _, _>::new(bus, Max17263RegisterResolver::new(Ohms(0.010)));
+        let snapshot = gauge.snapshot().unwrap();
+        assert_eq!(gauge.destroy().transactions, 1);
+        assert_eq!(snapshot.rep_cap, MilliAmpHours(1250.0));
+        assert_eq!(snapshot.rep_soc, Percent(50.0));
+        assert_eq!(snapshot.full_cap_rep, MilliAmpHours(2500.0));
+        assert_eq!(snapshot.tte, None);
+        assert_eq!(snapshot.ttf, None);
+        assert!((snapshot.voltage.0 - 3.7).abs() < 1e-3);
+        assert!((snapshot.average_voltage.0 - 3.7).abs() < 1e-3);
+        assert_eq!(snapshot.current, Amps(0.0));
+        assert_eq!(snapshot.average_current, Amps(-0.5));
+        assert_eq!(snapshot.temperature, Celsius(25.0));
+        assert!((snapshot.cycles - 46.56).abs() < 1e-9);
+        assert_eq!(snapshot.age, Percent(100.0));
+        assert!(snapshot.status.contains(Status::POR));
+    }
+
+    #[test]
+    fn battery_charge_status() {
+        let clock = SimClock::new();
+        let mut simulator = Max1726xSimulator::new(&clock);
+        // Charging at 0.5A, 2h to full
+        simulator.set_register(Register::AVG_CURRENT, 0x0C80);
+        simulator.set_register(Register::TTF, 0x0500);
+        let mut gauge = gauge(simulator);
+        let status = gauge.battery_charge_status().unwrap();
+        assert_eq!(status.rep_cap, MilliAmpHours(1250.0));
+        assert_eq!(status.rep_soc, Percent(50.0));
+        assert_eq!(status.full_cap_rep, MilliAmpHours(2500.0));
+        assert_eq!(status.tte, None);
+        assert_eq!(status.ttf, Some(Duration::from_secs(7200)));
+        assert_eq!(status.state, ChargeState::Charging);
+        assert_eq!(status.raw().rep_cap, 0x09C4);
+        assert_eq!(status.raw().ttf, 0x0500);
+
+        let (mut simulator, resolver) = gauge.release();
+        simulator.set_register(Register::F_STAT, FStat::REL_DT.bits());
+        let mut relaxed = Max1726x::<Max17263, _, _>::new(simulator, resolver);
+        let status = relaxed.battery_charge_status().unwrap();
+        assert_eq!(status.state, ChargeState::Relaxed);
+    }
+
+    #[test]
+    fn time_to_empty_and_full() {
+        let clock = SimClock::new();
+        let mut simulator = Max1726xSimulator::new(&clock);
+        simulator.set_register(OutputRegister::TTE, 0x0001);
+        let mut gauge = gauge(simulator);
+        assert_eq!(
+            gauge.time_to_empty().unwrap(),
+            Some(Duration::from_millis(5625))
+        );
+        // 0xFFFF, not 102 hours
+        assert_eq!(gauge.time_to_full().unwrap(), None);
+        assert_eq!(gauge.ttf_register().unwrap().to_duration(), None);
+        assert_eq!(
+            gauge.tte_register().unwrap().to_duration(),
+            Some(Duration::from_millis(5625))
+        );
+    }
+
+    #[test]
+    fn averaged_measurements() {
+        let clock = SimClock::new();
+        let mut simulator = Max1726xSimulator::new(&clock);
+        // Discharging at 1A and 3.7W at 10mΩ, averages at 0.5A and 1.85W
+        simulator.set_register(0x0A, 0xE700);
+        simulator.set_register(Register::POWER, 0xEDEF);
+        simulator.set_register(Register::AVG_CURRENT, 0xF380);
+        simulator.set_register(Register::AVG_POWER, 0xF6F8);
+        let mut gauge = gauge(simulator);
+        let measurement = gauge.measurement().unwrap();
+        assert!((measurement.voltage.0 - 3.7).abs() < 1e-3);
+        assert_eq!(measurement.current, Amps(-1.0));
+        assert_eq!(measurement.temperature, Celsius(25.0));
+        assert!((measurement.power.0 + 3.7).abs() < 1e-3);
+        let average = gauge.average_measurement().unwrap();
+        assert!((average.voltage.0 - 3.7).abs() < 1e-3);
+        assert_eq!(average.current, Amps(-0.5));
+        assert_eq!(average.temperature, Celsius(25.0));
+        assert!((average.power.0 + 1.85).abs() < 1e-3);
+    }
+
+    #[test]
+    fn max_min() {
+        let clock = SimClock::new();
+        let mut simulator = Max1726xSimulator::new(&clock);
+        // 3.0V to 4.2V, -2A to 1.2A at 10mΩ, -10°C to 45°C
+        simulator.set_register(Register::MAX_MIN_VOLT, 0xD296);
+        simulator.set_register(Register::MAX_MIN_CURR, 0x1ECE);
+        simulator.set_register(Register::MAX_MIN_TEMP, 0x2DF6);
+        let mut gauge = gauge(simulator);
+        assert_eq!(
+            gauge.voltage_max_min().unwrap(),
+            Some(MaxMin {
+                min: Volts(3.0),
+                max: Volts(4.2)
+            })
+        );
+        assert_eq!(
+            gauge.current_max_min().unwrap(),
+            Some(MaxMin {
+                min: Amps(-2.0),
+                max: Amps(1.2)
+            })
+        );
+        assert_eq!(
+            gauge.temperature_max_min().unwrap(),
+            Some(MaxMin {
+                min: Celsius(-10.0),
+                max: Celsius(45.0)
+            })
+        );
+
+        gauge.reset_max_min().unwrap();
+        assert_eq!(u16::from(gauge.max_min_volt_register().unwrap()), 0x00FF);
+        assert_eq!(gauge.voltage_max_min().unwrap(), None);
+        assert_eq!(gauge.current_max_min().unwrap(), None);
+        assert_eq!(gauge.temperature_max_min().unwrap(), None);
+    }
+
+    #[test]
+    fn handle_alert() {
+        let clock = SimClock::new();
+        let mut simulator = Max1726xSimulator::new(&clock);
+        simulator.set_register(
+            Register::STATUS,
+            (Status::POR | Status::BST | Status::VMN | Status::D_SOC_I).bits(),
+        );
+        let mut gauge = gauge(simulator);
+        let events = gauge.handle_alert().unwrap();
+        assert_eq!(
+            events.iter().collect::<Vec<_>>(),
+            [
+                AlertEvent::PowerOnReset,
+                AlertEvent::StateOfChargeStep,
+                AlertEvent::VoltageLow
+            ]
+        );
+        // POR and Bst are left alone
+        assert_eq!(gauge.status_register().unwrap(), Status::POR | Status::BST);
+        assert!(gauge
+            .handle_alert()
+            .unwrap()
+            .iter()
+            .eq([AlertEvent::PowerOnReset]));
+    }
+
+    #[test]
+    fn poll_alert() {
+        let clock = SimClock::new();
+        let mut simulator = Max1726xSimulator::new(&clock);
+        simulator.set_register(Register::STATUS, Status::TMX.bits());
+        let mut gauge = gauge(simulator);
+        assert!(gauge.poll_alert(&mut AlertPin(false)).unwrap().is_empty());
+        assert_eq!(gauge.status_register().unwrap(), Status::TMX);
+        let events = gauge.poll_alert(&mut AlertPin(true)).unwrap();
+        assert!(events.contains(AlertEvent::TemperatureHigh));
+        assert!(gauge.status_register().unwrap().is_empty());
+    }
+
+    #[test]
+    fn status_2_is_not_an_alert() {
+        let clock = SimClock::new();
+        let mut simulator = Max1726xSimulator::new(&clock);
+        simulator.set_register(Register::STATUS, Status::SMX.bits());
+        simulator.set_register(Register::STATUS_2, Status2::FULL_DET.bits());
+        let mut gauge = gauge(simulator);
+        assert_eq!(
+            gauge.status_2_register().unwrap(),
+            Status2::HIB | Status2::FULL_DET
+        );
+        assert!(gauge
+            .handle_alert()
+            .unwrap()
+            .iter()
+            .eq([AlertEvent::StateOfChargeHigh]));
+        // FullDet is left for the application to clear
+        assert_eq!(
+            gauge.status_2_register().unwrap(),
+            Status2::HIB | Status2::FULL_DET
+        );
+        gauge.exit_hibernate().unwrap();
+        assert_eq!(gauge.status_2_register().unwrap(), Status2::FULL_DET);
+    }
+
+    #[test]
+    fn learned_parameters_survive_por() {
+        let clock = SimClock::new();
+        let mut gauge = gauge(Max1726xSimulator::new(&clock));
+        gauge.ez_config(clock.delay(), ez_config()).unwrap();
+        for (register, data) in [
+            (Register::R_COMP_0, 0x0065),
+            (Register::TEMP_CO, 0x1F3A),
+            (Register::FULL_CAP_REP, 0x1200),
+            (Register::CYCLES, 0x0123),
+            (Register::FULL_CAP_NOM, 0x1300),
+            (Register::QR_TABLE_20, 0x0A00),
+        ] {
+            gauge.write_register(register, data).unwrap();
+        }
+        let blob = gauge.save_learned_parameters().unwrap().to_bytes();
+
+        gauge.firmware_restart().unwrap();
+        gauge.ez_config(clock.delay(), ez_config()).unwrap();
+        let learned = LearnedParameters::from_bytes(&blob).unwrap();
+        assert_ne!(gauge.save_learned_parameters().unwrap(), learned);
+        let start_us = clock.now_us();
+        gauge
+            .restore_learned_parameters(clock.delay(), &learned)
+            .unwrap();
+        assert!(clock.now_us() - start_us >= 700_000);
+        assert_eq!(gauge.save_learned_parameters().unwrap(), learned);
+        // 50% MixSOC of the restored FullCapNom
+        assert_eq!(
+            gauge.read_register_as_u16(Register::MIX_CAP).unwrap(),
+            0x0980
+        );
+        assert_eq!(
+            gauge.read_register_as_u16(Register::D_Q_ACC).unwrap(),
+            0x0130
+        );
+        assert_eq!(
+            gauge.read_register_as_u16(Register::D_P_ACC).unwrap(),
+            0x0C80
+        );
+    }
+
+    #[test]
+    fn firmware_restart() {
+        let clock = SimClock::new();
+        let mut gauge = gauge(Max1726xSimulator::new(&clock));
+        gauge.ez_config(clock.delay(), ez_config()).unwrap();
+        assert!((gauge.status_register().unwrap() & Status::POR).is_empty());
+        gauge.firmware_restart().unwrap();
+        assert!(gauge.status_register().unwrap().contains(Status::POR));
+        assert!(!gauge.config_2_register().unwrap().por_cmd());
+    }
+
+    #[test]
+    fn owned_bus_is_released() {
+        let clock = SimClock::new();
+        let mut gauge = gauge(Max1726xSimulator::new(&clock));
+        gauge.write_register(Register::DESIGN_CAP, 0x1234).unwrap();
+        let (simulator, _resolver) = gauge.release();
+        assert_eq!(simulator.register(Register::DESIGN_CAP), 0x1234);
+    }
+
+    #[test]
+    fn shared_bus() {
+        let clock = SimClock::new();
+        let bus = RefCell::new(Max1726xSimulator::new(&clock));
+        let mut gauge = Max1726x::<Max17263, _, _>::new(
+            RefCellDevice::new(&bus),
+            Max17263RegisterResolver::new(Ohms(0.010)),
+        );
+        // Another driver on the same bus
+        let mut other = RefCellDevice::new(&bus);
+        assert!((gauge.battery_voltage().unwrap().0 - 3.7).abs() < 1e-3);
+        other
+            .write(ADDR, &[Register::DESIGN_CAP, 0x34, 0x12])
+            .unwrap();
+        assert_eq!(
+            gauge.read_register_as_u16(Register::DESIGN_CAP).unwrap(),
+            0x1234
+        );
+        gauge.destroy();
+    }
+
+    #[test]
+    fn multi_cell_pack() {
+        let clock = SimClock::new();
+        let mut simulator = Max1726xSimulator::new(&clock);
+        // 3S pack at 11.1V
+        simulator.set_register(Max17263::BATT, 0x22B0);
+        let mut gauge = gauge(simulator);
+        assert_eq!(gauge.cell_count().unwrap(), 1);
+        assert!(matches!(
+            gauge.verify_cell_count(3),
+            Err(Error::CellCountMismatch {
+                expected: 3,
+                configured: 1
+            })
+        ));
+        gauge.set_cell_count(clock.delay(), 3).unwrap();
+        gauge.verify_cell_count(3).unwrap();
+        assert_eq!(gauge.pack_cfg_register().unwrap().n_cells(), 2);
+        assert!(matches!(
+            gauge.set_cell_count(clock.delay(), 0),
+            Err(Error::InvalidCellCount(0))
+        ));
+        assert!(matches!(
+            gauge.set_cell_count(clock.delay(), MAX_CELLS + 1),
+            Err(Error::InvalidCellCount(16))
+        ));
+        assert_eq!(gauge.cell_count().unwrap(), 3);
+        assert!((gauge.pack_voltage().unwrap().0 - 11.1).abs() < 1e-9);
+        assert!((gauge.cell_voltage().unwrap().0 - 3.7).abs() < 1e-3);
+        assert!((gauge.average_cell_voltage().unwrap().0 - 3.7).abs() < 1e-3);
+    }
+
+    #[test]
+    fn ez_config_with_pack_cfg() {
+        let clock = SimClock::new();
+        let mut gauge = gauge(Max1726xSimulator::new(&clock));
+        let pack_cfg = PackCfg::from(0x3C00).with_cells(2);
+        assert!(matches!(
+            gauge.ez_config_with_pack_cfg(clock.delay(), pack_cfg.with_n_cells(15), ez_config()),
+            Err(Error::InvalidCellCount(16))
+        ));
+        gauge
+            .ez_config_with_pack_cfg(clock.delay(), pack_cfg, ez_config())
+            .unwrap();
+        assert_eq!(u16::from(gauge.pack_cfg_register().unwrap()), 0x3C01);
+        gauge.verify_cell_count(2).unwrap();
+        let simulator = gauge.destroy();
+        assert_eq!(simulator.register(Register::DESIGN_CAP), 5000);
+        assert_eq!(simulator.register(Register::STATUS) & Status::POR.bits(), 0);
+    }
+
+    #[test]
+    fn cell_resistance_and_open_circuit_voltage() {
+        use crate::max17263::registers::Max17263FixedPointResolver;
+        use crate::units::fixed::{MicroOhms, MilliOhms, MilliVolts};
+
+        let clock = SimClock::new();
+        let mut simulator = Max1726xSimulator::new(&clock);
+        // 125mΩ, 3.6V
+        simulator.set_register(Register::R_CELL, 0x0200);
+        simulator.set_register(Register::V_FOCV, 0xB400);
+        let mut gauge = gauge(simulator);
+        assert_eq!(gauge.r_cell_register().unwrap().resistance(), 0x0200);
+        assert_eq!(gauge.cell_resistance().unwrap(), Ohms(0.125));
+        assert!((gauge.open_circuit_voltage().unwrap().0 - 3.6).abs() < 1e-9);
+
+        let (simulator, _) = gauge.release();
+        let mut gauge = Max1726x::<Max17263, _, _>::new(
+            simulator,
+            Max17263FixedPointResolver::new(MicroOhms(10_000)).unwrap(),
+        );
+        assert_eq!(gauge.cell_resistance().unwrap(), MilliOhms(125));
+        assert_eq!(gauge.open_circuit_voltage().unwrap(), MilliVolts(3600));
+    }
+
+    #[test]
+    fn modify() {
+        let clock = SimClock::new();
+        let mut simulator = Max1726xSimulator::new(&clock);
+        // Reserved bits 0, 4 and 14 set
+        simulator.set_register(Register::CONFIG_2, 0x7659);
+        let mut gauge = gauge(simulator);
+        gauge
+            .modify(|config_2: Config2| config_2.with_d_so_cen(true))
+            .unwrap();
+        assert_eq!(
+            gauge.read_register_as_u16(Register::CONFIG_2).unwrap(),
+            0x76D9
+        );
+
+        gauge
+            .modify_verified(clock.delay(), |model_cfg: ModelCfg| {
+                model_cfg.with_v_chg(true)
+            })
+            .unwrap();
+        assert!(gauge
+            .read_register_as_bitfield::<ModelCfg>()
+            .unwrap()
+            .v_chg());
+
+        // VFOCV is read-only, so the write doesn't stick
+        assert!(matches!(
+            gauge.modify_verified(clock.delay(), |v_focv: VFocv| v_focv.with_voltage(0)),
+            Err(Error::WriteNotVerified {
+                register: Register::V_FOCV,
+                write: 0x0000,
+                read: 0xB900,
+            })
+        ));
+    }
+
+    #[test]
+    fn state_of_health() {
+        let clock = SimClock::new();
+        let mut simulator = Max1726xSimulator::new(&clock);
+        // 100 cycles, 2250mAh of 2500mAh left, 500 cycles of life forecast
+        simulator.set_register(Register::CYCLES, 0x0271);
+        simulator.set_register(Register::DESIGN_CAP, 0x1388);
+        simulator.set_register(Register::FULL_CAP_NOM, 0x1194);
+        simulator.set_register(Register::AGE, 0x5A00);
+        simulator.set_register(Max17263::AGE_FORECAST, 0x0C35);
+        let mut gauge = gauge(simulator);
+        assert!((gauge.cycles().unwrap() - 100.0).abs() < 1e-9);
+        assert_eq!(gauge.age().unwrap(), Percent(90.0));
+        assert_eq!(gauge.design_cap().unwrap(), MilliAmpHours(2500.0));
+        assert_eq!(gauge.full_cap_nom().unwrap(), MilliAmpHours(2250.0));
+        let health = gauge.state_of_health().unwrap();
+        assert!((health.cycles - 100.0).abs() < 1e-9);
+        assert_eq!(health.design_cap, MilliAmpHours(2500.0));
+        assert_eq!(health.full_cap_nom, MilliAmpHours(2250.0));
+        assert_eq!(health.age, Percent(90.0));
+        assert_eq!(health.capacity_fade, Percent(10.0));
+        assert!((health.remaining_cycles.unwrap() - 400.0).abs() < 1e-9);
+
+        // Only the MAX17263 forecasts the cycle life
+        let (simulator, resolver) = gauge.release();
+        let mut max17260 =
+            Max1726x::<crate::max17260::registers::Register, _, _>::new(simulator, resolver);
+        let health = max17260.state_of_health().unwrap();
+        assert_eq!(health.capacity_fade, Percent(10.0));
+        assert_eq!(health.remaining_cycles, None);
+
+        // No forecast yet
+        let (mut simulator, resolver) = max17260.release();
+        simulator.set_register(Max17263::AGE_FORECAST, 0x0000);
+        let mut gauge = Max1726x::<Max17263, _, _>::new(simulator, resolver);
+        assert_eq!(gauge.state_of_health().unwrap().remaining_cycles, None);
+    }
+
+    #[test]
+    fn fixed_point_resolver() {
+        use crate::max17263::registers::Max17263FixedPointResolver;
+        use crate::units::fixed::{
+            CentiCelsius, MicroAmps, MicroOhms, MilliAmpHours, MilliPercent, MilliVolts, MilliWatts,
+        };
+
+        let clock = SimClock::new();
+        let mut simulator = Max1726xSimulator::new(&clock);
+        // Discharging at 1A, 3.7W
+        simulator.set_register(0x0A, 0xE700);
+        simulator.set_register(Register::POWER, 0xEDEF);
+        let mut float_gauge = gauge(simulator);
+        let float = float_gauge.measurement().unwrap();
+        let (simulator, _) = float_gauge.release();
+        let mut gauge = Max1726x::<Max17263, _, _>::new(
+            simulator,
+            Max17263FixedPointResolver::new(MicroOhms(10_000)).unwrap(),
+        );
+        let measurement = gauge.measurement().unwrap();
+        assert_eq!(
+            measurement,
+            Measurement {
+                voltage: MilliVolts(3700),
+                current: MicroAmps(-1_000_000),
+                temperature: CentiCelsius(2500),
+                power: MilliWatts(-3700),
+            }
+        );
+        assert_eq!(float.current, Amps(-1.0));
+        assert!((float.power.0 + 3.7).abs() < 1e-9);
+        let snapshot = gauge.snapshot().unwrap();
+        assert_eq!(snapshot.rep_soc, MilliPercent(50_000));
+        assert_eq!(snapshot.full_cap_rep, MilliAmpHours(2500));
+
+        let ez_config = EzConfig {
+            charge_voltage: MilliVolts(4400),
+            design_cap: MilliAmpHours(2500),
+            i_chg_term: MicroAmps(100_000),
+            v_empty: VEmpty::init(3300, 3880),
+        };
+        gauge.ez_config(clock.delay(), ez_config).unwrap();
+        assert!(gauge
+            .read_register_as_bitfield::<ModelCfg>()
+            .unwrap()
+            .v_chg());
+        let simulator = gauge.destroy();
+        // Same registers as the float resolver writes
+        assert_eq!(simulator.register(Register::DESIGN_CAP), 5000);
+        assert_eq!(simulator.register(Register::I_CHG_TERM), 640);
     }
 }
@@ -0,0 +1,380 @@
+/// Async variant of the driver in `comms`, built on `embedded-hal-async`.
+///
+/// The register map, conversions, the EZ config and hibernate-exit register writes and the POR
+/// mask are shared with the blocking driver, and the initialisation steps mirror its helpers one
+/// to one, so the two stay in sync; only the bus and delay calls are awaited here.
+use crate::{
+    comms::{
+        register_bytes_to_u16, status_without_por, write_payload, BatteryChargeStatus, EzConfig,
+        PollTimer, RawChargeStatus, Timeouts, ADDR, EXIT_HIBERNATE, WRITE_VERIFY_RETRIES,
+    },
+    error::{Error, TimeoutStep},
+    registers::{FStat, HibCfg, ModelCfg, OutputRegister, Register, Status, VEmpty},
+    traits::{BitField, Current, Model, Percentage, RegisterResolver, Temperature, Voltage},
+};
+use defmt::debug;
+use embedded_hal::i2c;
+use embedded_hal_async::delay::DelayNs;
+use embedded_hal_async::i2c::I2c;
+
+#[derive(Debug, defmt::Format)]
+pub struct Max1726xAsync<M, I2C, R>
+where
+    R: RegisterResolver,
+{
+    i2c: I2C,
+    register_resolver: R,
+    timeouts: Timeouts,
+    _phantom: core::marker::PhantomData<M>,
+}
+
+impl<M, I2C, E, R> Max1726xAsync<M, I2C, R>
+where
+    M: Model,
+    I2C: I2c<Error = E>,
+    E: i2c::Error + 'static,
+    R: RegisterResolver,
+{
+    /// Create a new driver instance.
+    ///
+    /// The bus is taken by value. Pass `&mut i2c` to keep ownership, or an `embedded-hal-bus`
+    /// device (e.g. `RefCellDevice`, `CriticalSectionDevice`) to share the bus with other drivers.
+    pub fn new(i2c: I2C, register_resolver: R) -> Self {
+        Self {
+            i2c,
+            register_resolver,
+            timeouts: Timeouts::default(),
+            _phantom: core::marker::PhantomData,
+        }
+    }
+
+    /// Set the maximum wait times for the polling loops in `ez_config`
+    pub fn set_timeouts(&mut self, timeouts: Timeouts) {
+        self.timeouts = timeouts;
+    }
+
+    /// Get the maximum wait times for the polling loops in `ez_config`
+    pub fn timeouts(&self) -> Timeouts {
+        self.timeouts
+    }
+
+    /// Destroy the driver instance, returning the I2C bus and the register resolver.
+    pub fn release(self) -> (I2C, R) {
+        (self.i2c, self.register_resolver)
+    }
+
+    /// Destroy the driver instance, returning the I2C bus.
+    pub fn destroy(self) -> I2C {
+        self.i2c
+    }
+
+    /// Write a register - data should be written little endian/LSB first
+    pub async fn write_register(&mut self, register: u8, data: u16) -> Result<(), Error<E>> {
+        let payload = write_payload(register, data);
+        debug!("Data {:X} written to register {:X}", data, register);
+        self.i2c.write(ADDR, &payload).await.map_err(Error::I2c)
+    }
+
+    pub async fn write_and_verify_register<D>(
+        &mut self,
+        register: u8,
+        data: u16,
+        mut delay: D,
+    ) -> Result<(), Error<E>>
+    where
+        D: DelayNs,
+    {
+        let mut attempt: u8 = 0;
+        loop {
+            self.write_register(register, data).await?;
+            delay.delay_ms(1).await;
+            let read_value = self.read_register_as_u16(register).await?;
+            if data == read_value {
+                return Ok(());
+            }
+            attempt += 1;
+            if attempt > WRITE_VERIFY_RETRIES {
+                return Err(Error::WriteNotVerified {
+                    register,
+                    write: data,
+                    read: read_value,
+                });
+            }
+        }
+    }
+
+    pub async fn write_bitfield_to_register<B>(&mut self, bitfield: B) -> Result<(), Error<E>>
+    where
+        B: Into<u16> + BitField,
+    {
+        let data = bitfield.into();
+        self.write_register(B::REGISTER, data).await
+    }
+
+    /// Read a register - return the bytes in the order that they are received (litte-endian/LSB
+    /// first)
+    pub async fn read_register(&mut self, register: u8) -> Result<[u8; 2], Error<E>> {
+        let mut data = [0u8; 2];
+        self.i2c
+            .write_read(ADDR, &[register], &mut data)
+            .await
+            .map_err(Error::I2c)?;
+        debug!("Data {:X} read from register {:X}", data, register);
+        Ok(data)
+    }
+
+    /// Read a register into a u16
+    pub async fn read_register_as_u16(&mut self, register: u8) -> Result<u16, Error<E>> {
+        let data = self.read_register(register).await?;
+        Ok(register_bytes_to_u16(data))
+    }
+
+    /// Read a register and convert to the given bitfield
+    pub async fn read_register_as_bitfield<B>(&mut self) -> Result<B, Error<E>>
+    where
+        B: From<u16> + BitField,
+    {
+        Ok(B::from(self.read_register_as_u16(B::REGISTER).await?))
+    }
+
+    /// Battery voltage in V
+    pub async fn battery_voltage(&mut self) -> Result<Voltage<R>, Error<E>> {
+        let register = self.read_register_as_u16(M::V_CELL).await?;
+        Ok(self.register_resolver.register_to_voltage(register))
+    }
+
+    /// Battery charge/discharge current in A
+    pub async fn battery_current(&mut self) -> Result<Current<R>, Error<E>> {
+        let register = self.read_register_as_u16(M::CURRENT).await?;
+        Ok(self.register_resolver.register_to_current(register))
+    }
+
+    /// Battery temperature in degrees C
+    pub async fn battery_temperature(&mut self) -> Result<Temperature<R>, Error<E>> {
+        let register = self.read_register_as_u16(M::TEMP).await?;
+        Ok(self.register_resolver.register_to_temperature(register))
+    }
+
+    /// Battery state of charge as a percentage
+    pub async fn battery_state_of_charge(&mut self) -> Result<Percentage<R>, Error<E>> {
+        let register = self.read_register_as_u16(OutputRegister::REP_SOC).await?;
+        Ok(self.register_resolver.register_to_percentage(register))
+    }
+
+    /// Get Status register (00h)
+    pub async fn status_register(&mut self) -> Result<Status, Error<E>> {
+        Ok(Status::from_bits_truncate(
+            self.read_register_as_u16(Register::STATUS).await?,
+        ))
+    }
+    /// Get FStat Register (3Dh)
+    pub async fn fstat_register(&mut self) -> Result<FStat, Error<E>> {
+        Ok(FStat::from_bits_truncate(
+            self.read_register_as_u16(Register::F_STAT).await?,
+        ))
+    }
+    /// Get HibCfg Register (BAh)
+    pub async fn hib_cfg_register(&mut self) -> Result<HibCfg, Error<E>> {
+        let msb_bytes = self.read_register_as_u16(Register::HIB_CFG).await?;
+        Ok(HibCfg::from_bytes(msb_bytes.to_le_bytes()))
+    }
+
+    /// Get VEmpty Register (3Ah)
+    pub async fn v_empty_register(&mut self) -> Result<VEmpty, Error<E>> {
+        let msb_bytes = self.read_register_as_u16(Register::V_EMPTY).await?;
+        Ok(VEmpty::from_bytes(msb_bytes.to_le_bytes()))
+    }
+
+    /// Get the battery charge status
+    pub async fn battery_charge_status(
+        &mut self,
+    ) -> Result<BatteryChargeStatus<R::Units>, Error<E>> {
+        let raw = RawChargeStatus {
+            rep_cap: self.read_register_as_u16(OutputRegister::REP_CAP).await?,
+            rep_soc: self.read_register_as_u16(OutputRegister::REP_SOC).await?,
+            full_cap_rep: self.read_register_as_u16(Register::FULL_CAP_REP).await?,
+            tte: self.read_register_as_u16(OutputRegister::TTE).await?,
+            ttf: self.read_register_as_u16(Register::TTF).await?,
+            avg_current: self.read_register_as_u16(Register::AVG_CURRENT).await?,
+            f_stat: self.read_register_as_u16(Register::F_STAT).await?,
+        };
+        Ok(raw.resolve(&self.register_resolver))
+    }
+
+    /// Setup the fuel gauge - see `Max1726x::ez_config` for the sequence.
+    /// The polling loops await the delay instead of blocking, so other tasks keep running
+    /// while the gauge is busy. They give up with `Error::Timeout` after the limits set with
+    /// `set_timeouts`; dropping the future cancels the sequence at the next await point.
+    pub async fn ez_config<D>(
+        &mut self,
+        mut delay: D,
+        ez_config: EzConfig<R::Units>,
+    ) -> Result<(), Error<E>>
+    where
+        D: DelayNs,
+    {
+        defmt::info!(
+            "Starting MAX1726x EZ Config with configuration: {}",
+            ez_config
+        );
+        // Step 0: check for POR
+        let status = self.status_register().await?;
+        defmt::info!("Initial status: {}", status);
+        if (status & Status::POR).is_empty() {
+            defmt::info!("Power On Reset (POR) not detected.");
+            // Go to step 3.2
+        } else {
+            // Step 1. Delay until FSTAT.DNR bit == 0
+            self.wait_for_data_ready(&mut delay).await?;
+
+            // Step 2. Initialise configuration
+            let hib_cfg = self.exit_hibernate().await?;
+            self.write_ez_config(&ez_config).await?;
+            self.wait_for_model_refresh(&mut delay).await?;
+
+            defmt::info!("Restoring Hibernate Mode");
+            self.write_bitfield_to_register(hib_cfg).await?;
+        }
+        // Step 3: Initialization Complete
+        self.clear_por(delay).await?;
+        defmt::info!("Initialization Complete");
+
+        let battery_charge_status = self.battery_charge_status().await?;
+        defmt::info!("Battery charge status: {}", battery_charge_status);
+
+        Ok(())
+    }
+
+    /// 2.1 OPTION 1 EZ Config (No INI file is needed)
+    async fn write_ez_config(&mut self, ez_config: &EzConfig<R::Units>) -> Result<(), Error<E>> {
+        defmt::info!("Option 1 EZ Config");
+        for (register, data) in ez_config.register_writes(&self.register_resolver) {
+            self.write_register(register, data).await?;
+        }
+        Ok(())
+    }
+
+    /// Step 1: wait until FStat.DNR clears
+    async fn wait_for_data_ready<D>(&mut self, delay: &mut D) -> Result<(), Error<E>>
+    where
+        D: DelayNs,
+    {
+        defmt::info!("Delaying for DNR bit to clear...");
+        let mut timer = PollTimer::new(TimeoutStep::DataNotReady, self.timeouts.data_ready_ms);
+        while !(self.fstat_register().await? & FStat::DNR).is_empty() {
+            delay.delay_ms(timer.tick()?).await;
+        }
+        Ok(())
+    }
+
+    /// Exit hibernate mode, returning the original HibCfg value to restore afterwards
+    async fn exit_hibernate(&mut self) -> Result<HibCfg, Error<E>> {
+        let hib_cfg = self.hib_cfg_register().await?;
+        defmt::info!("HibCFG: {}", hib_cfg);
+        defmt::info!("Exiting Hibernate Mode");
+        for (register, data) in EXIT_HIBERNATE {
+            self.write_register(register, data).await?;
+        }
+        Ok(hib_cfg)
+    }
+
+    /// Wait until ModelCfg.Refresh clears after the model has been written
+    async fn wait_for_model_refresh<D>(&mut self, delay: &mut D) -> Result<(), Error<E>>
+    where
+        D: DelayNs,
+    {
+        defmt::info!("Waiting for ModelCFG.Refresh to clear...");
+        let mut timer = PollTimer::new(TimeoutStep::ModelRefresh, self.timeouts.model_refresh_ms);
+        while self
+            .read_register_as_bitfield::<ModelCfg>()
+            .await?
+            .refresh()
+        {
+            delay.delay_ms(timer.tick()?).await;
+        }
+        Ok(())
+    }
+
+    /// Step 3: clear the POR bit to indicate that the model and parameters are successfully
+    /// loaded
+    async fn clear_por<D>(&mut self, delay: D) -> Result<(), Error<E>>
+    where
+        D: DelayNs,
+    {
+        defmt::info!("Clearing Power On Reset (POR) bit");
+        let status = status_without_por(self.read_register_as_u16(Register::STATUS).await?);
+        self.write_and_verify_register(Register::STATUS, status, delay)
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::max17263::registers::{Max17263RegisterResolver, Register as Max17263};
+    use crate::simulator::{Max1726xSimulator, SimClock};
+    use crate::units::{Amps, MilliAmpHours, Ohms, Volts};
+    use embassy_futures::block_on;
+
+    #[test]
+    fn read_and_write_registers() {
+        let clock = SimClock::new();
+        let mut gauge = Max1726xAsync::<Max17263, _, _>::new(
+            Max1726xSimulator::new(&clock),
+            Max17263RegisterResolver::new(Ohms(0.010)),
+        );
+        block_on(async {
+            let voltage = gauge.battery_voltage().await.unwrap();
+            assert!((voltage.0 - 3.7).abs() < 1e-3);
+
+            gauge
+                .write_and_verify_register(Register::DESIGN_CAP, 0x1234, clock.delay())
+                .await
+                .unwrap();
+            assert_eq!(
+                gauge.read_register(Register::DESIGN_CAP).await.unwrap(),
+                [0x34, 0x12]
+            );
+        });
+    }
+
+    #[test]
+    fn ez_config_after_por() {
+        let clock = SimClock::new();
+        let mut gauge = Max1726xAsync::<Max17263, _, _>::new(
+            Max1726xSimulator::new(&clock),
+            Max17263RegisterResolver::new(Ohms(0.010)),
+        );
+        let ez_config = EzConfig {
+            charge_voltage: Volts(4.2),
+            design_cap: MilliAmpHours(2500.0),
+            i_chg_term: Amps(0.1),
+            v_empty: VEmpty::init(3300, 3880),
+        };
+        block_on(gauge.ez_config(clock.delay(), ez_config)).unwrap();
+        let simulator = gauge.destroy();
+        assert_eq!(simulator.register(Register::DESIGN_CAP), 5000);
+        assert_eq!(simulator.register(Register::STATUS) & Status::POR.bits(), 0);
+    }
+
+    #[test]
+    fn data_not_ready_times_out() {
+        let clock = SimClock::new();
+        let mut simulator = Max1726xSimulator::new(&clock);
+        simulator.set_data_ready_time_ms(None);
+        let mut gauge = Max1726xAsync::<Max17263, _, _>::new(
+            simulator,
+            Max17263RegisterResolver::new(Ohms(0.010)),
+        );
+        let ez_config = EzConfig {
+            charge_voltage: Volts(4.2),
+            design_cap: MilliAmpHours(2500.0),
+            i_chg_term: Amps(0.1),
+            v_empty: VEmpty::init(3300, 3880),
+        };
+        assert!(matches!(
+            block_on(gauge.ez_config(clock.delay(), ez_config)),
+            Err(Error::Timeout(TimeoutStep::DataNotReady))
+        ));
+    }
+}
@@ -1,5 +1,5 @@
 use core::fmt::Debug;
-use embedded_hal::i2c;
+use embedded_hal::{digital, i2c};
 
 /// The error type used by this library.
 ///
@@ -18,6 +18,33 @@ where
     /// * `write`: register value written
     /// * `read`: register value read
     WriteNotVerified { register: u8, write: u16, read: u16 },
+
+    /// A polling loop ran out of time waiting for the fuel gauge
+    /// * `0`: the step that timed out
+    Timeout(TimeoutStep),
+
+    /// The model table still reads back after model access was locked
+    ModelNotLocked,
+
+    /// Reading the ALRT input pin failed
+    Pin(digital::ErrorKind),
+
+    /// A cell count outside 1 to 15 was given for a multi-cell pack
+    InvalidCellCount(u8),
+
+    /// PackCfg.NCELLS doesn't match the expected number of cells
+    /// * `expected`: the number of cells in the pack
+    /// * `configured`: the number of cells in PackCfg
+    CellCountMismatch { expected: u8, configured: u8 },
+}
+
+/// The polling steps of the initialisation sequence that can time out
+#[derive(Debug, Clone, Copy, PartialEq, Eq, defmt::Format)]
+pub enum TimeoutStep {
+    /// Waiting for FStat.DNR (Data Not Ready) to clear after a POR (step 1)
+    DataNotReady,
+    /// Waiting for ModelCfg.Refresh to clear after the model was written (step 2)
+    ModelRefresh,
 }
 
 impl<E> Debug for Error<E>
@@ -28,7 +55,12 @@ where
         use Error::*;
         match self {
             I2c(err) => write!(f, "I2C error: {:?}", err.kind()),
-            WriteNotVerified{register, write, read} => write!(f, "Written data not verified to register {:x}. Regisiter value written: {:x}, read: {:x}", register,write, read)
+            WriteNotVerified{register, write, read} => write!(f, "Written data not verified to register {:x}. Regisiter value written: {:x}, read: {:x}", register,write, read),
+            Timeout(step) => write!(f, "Timed out waiting for the fuel gauge: {:?}", step),
+            ModelNotLocked => write!(f, "Model access not locked"),
+            Pin(kind) => write!(f, "ALRT pin error: {:?}", kind),
+            InvalidCellCount(cells) => write!(f, "Invalid cell count: {}", cells),
+            CellCountMismatch { expected, configured } => write!(f, "Expected {} cells, PackCfg configured for {}", expected, configured),
         }
     }
 }
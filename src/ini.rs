@@ -0,0 +1,286 @@
+/// Parser for ModelGauge m5 characterization .INI files, into a `CustomModel`.
+///
+/// The parser works on a `&str` and does not allocate. The accepted syntax:
+/// * `key = value` pairs, one per line. Keys are case-insensitive.
+/// * values are decimal or `0x`-prefixed hexadecimal register values
+/// * model table entries use the register address as the key, e.g. `0x80 = 0x9760`
+/// * `[section]` headers, blank lines and `;` or `#` comments are ignored
+/// * keys that aren't part of the model (e.g. `Title`, `ModelVersion`) are ignored
+///
+/// With the `std` feature, `load` reads and parses a file.
+use crate::{
+    model::{CustomModel, MODEL_TABLE_LEN},
+    registers::{ModelCfg, Register, VEmpty},
+};
+use core::fmt;
+
+/// The model parameter keys. The first `REQUIRED_KEYS` must be present.
+const KEYS: [&str; 15] = [
+    "DesignCap",
+    "IChgTerm",
+    "VEmpty",
+    "ModelCfg",
+    "RComp0",
+    "TempCo",
+    "QRTable00",
+    "QRTable10",
+    "QRTable20",
+    "QRTable30",
+    "LearnCfg",
+    "FilterCfg",
+    "RelaxCfg",
+    "MiscCfg",
+    "FullSOCThr",
+];
+
+/// Number of keys at the start of `KEYS` that every model must have
+const REQUIRED_KEYS: usize = 10;
+
+/// Reasons an .INI file is rejected. Line numbers start at 1.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, defmt::Format)]
+pub enum ParseError {
+    /// A line is not a section header, comment or `key = value` pair
+    MalformedLine { line: usize },
+    /// A value is not a decimal or `0x`-prefixed hexadecimal number
+    InvalidValue { line: usize },
+    /// A value does not fit a 16-bit register, or a register address key is outside the model
+    /// table (80h to AFh)
+    OutOfRange { line: usize },
+    /// A key is given more than once
+    DuplicateKey { line: usize },
+    /// A required key is missing
+    MissingKey(&'static str),
+    /// Only some of the model table registers are given
+    /// * `register`: the first missing register
+    IncompleteModelTable { register: u8 },
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use ParseError::*;
+        match self {
+            MalformedLine { line } => write!(f, "line {}: expected `key = value`", line),
+            InvalidValue { line } => write!(f, "line {}: invalid number", line),
+            OutOfRange { line } => write!(f, "line {}: value out of range", line),
+            DuplicateKey { line } => write!(f, "line {}: duplicate key", line),
+            MissingKey(key) => write!(f, "missing key {}", key),
+            IncompleteModelTable { register } => {
+                write!(f, "model table register 0x{:02X} missing", register)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseError {}
+
+/// Parse a number: `0x`-prefixed hexadecimal or decimal
+fn parse_number(text: &str, line: usize) -> Result<u32, ParseError> {
+    let parsed = match text.strip_prefix("0x").or_else(|| text.strip_prefix("0X")) {
+        Some(hex) => u32::from_str_radix(hex, 16),
+        None => text.parse(),
+    };
+    parsed.map_err(|_| ParseError::InvalidValue { line })
+}
+
+/// Parse the text of a characterization .INI file
+pub fn parse(text: &str) -> Result<CustomModel, ParseError> {
+    let mut values = [None; KEYS.len()];
+    let mut model_table = [None; MODEL_TABLE_LEN];
+    for (index, line) in text.lines().enumerate() {
+        let line_number = index + 1;
+        let line = line.split([';', '#']).next().unwrap_or_default().trim();
+        if line.is_empty() || (line.starts_with('[') && line.ends_with(']')) {
+            continue;
+        }
+        let (key, value) = line
+            .split_once('=')
+            .ok_or(ParseError::MalformedLine { line: line_number })?;
+        let key = key.trim();
+        let slot = if key.starts_with("0x") || key.starts_with("0X") {
+            let register = parse_number(key, line_number)?;
+            register
+                .checked_sub(u32::from(Register::MODEL_TABLE))
+                .and_then(|offset| model_table.get_mut(offset as usize))
+                .ok_or(ParseError::OutOfRange { line: line_number })?
+        } else if let Some(position) = KEYS.iter().position(|name| name.eq_ignore_ascii_case(key)) {
+            &mut values[position]
+        } else {
+            // Not part of the model, e.g. Title
+            continue;
+        };
+        let value = parse_number(value.trim(), line_number)?;
+        let value =
+            u16::try_from(value).map_err(|_| ParseError::OutOfRange { line: line_number })?;
+        if slot.replace(value).is_some() {
+            return Err(ParseError::DuplicateKey { line: line_number });
+        }
+    }
+
+    for (key, value) in KEYS.iter().zip(values).take(REQUIRED_KEYS) {
+        if value.is_none() {
+            return Err(ParseError::MissingKey(key));
+        }
+    }
+    let value = |index: usize| values[index].unwrap_or_default();
+
+    let model_table = if model_table.iter().all(Option::is_none) {
+        None
+    } else {
+        let mut table = [0u16; MODEL_TABLE_LEN];
+        for ((word, value), register) in table
+            .iter_mut()
+            .zip(model_table)
+            .zip(Register::MODEL_TABLE..)
+        {
+            *word = value.ok_or(ParseError::IncompleteModelTable { register })?;
+        }
+        Some(table)
+    };
+
+    Ok(CustomModel {
+        model_table,
+        design_cap: value(0),
+        i_chg_term: value(1),
+        v_empty: VEmpty::from(value(2)),
+        // Refresh is set by the driver when the model is loaded
+        model_cfg: ModelCfg::from(value(3)).with_refresh(false),
+        rcomp0: value(4),
+        temp_co: value(5),
+        qr_table: [value(6), value(7), value(8), value(9)],
+        learn_cfg: values[10],
+        filter_cfg: values[11],
+        relax_cfg: values[12],
+        misc_cfg: values[13],
+        full_soc_thr: values[14],
+    })
+}
+
+/// Reasons an .INI file can't be loaded
+#[cfg(feature = "std")]
+#[derive(Debug)]
+pub enum LoadError {
+    /// The file could not be read
+    Io(std::io::Error),
+    /// The file is not a valid characterization
+    Parse(ParseError),
+}
+
+#[cfg(feature = "std")]
+impl fmt::Display for LoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LoadError::Io(err) => write!(f, "failed to read INI file: {}", err),
+            LoadError::Parse(err) => write!(f, "invalid INI file: {}", err),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for LoadError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            LoadError::Io(err) => Some(err),
+            LoadError::Parse(err) => Some(err),
+        }
+    }
+}
+
+/// Read and parse a characterization .INI file
+#[cfg(feature = "std")]
+pub fn load<P>(path: P) -> Result<CustomModel, LoadError>
+where
+    P: AsRef<std::path::Path>,
+{
+    let text = std::fs::read_to_string(path).map_err(LoadError::Io)?;
+    parse(&text).map_err(LoadError::Parse)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FULL: &str = include_str!("../tests/fixtures/lifepo4_full.ini");
+    const SHORT: &str = include_str!("../tests/fixtures/short.ini");
+
+    #[test]
+    fn full_ini() {
+        let model = parse(FULL).unwrap();
+        let model_table = model.model_table.unwrap();
+        assert_eq!(model_table[0], 0x9760);
+        assert_eq!(model_table[0x1F], 0x0780);
+        assert_eq!(model_table[MODEL_TABLE_LEN - 1], 0x0100);
+        assert_eq!(model.design_cap, 0x1900);
+        assert_eq!(model.i_chg_term, 0x0333);
+        assert_eq!(u16::from(model.v_empty), 0x7D5F);
+        assert_eq!(model.model_cfg.model_id(), 6);
+        assert!(!model.model_cfg.refresh());
+        assert_eq!(model.rcomp0, 0x0035);
+        assert_eq!(model.temp_co, 0x1A2B);
+        assert_eq!(model.qr_table, [0x3A00, 0x1900, 0x0C80, 0x0880]);
+        assert_eq!(model.learn_cfg, Some(0x4486));
+        assert_eq!(model.filter_cfg, Some(0xCEA4));
+        assert_eq!(model.relax_cfg, Some(0x083B));
+        assert_eq!(model.misc_cfg, Some(0x0090));
+        assert_eq!(model.full_soc_thr, Some(0x5F05));
+    }
+
+    #[test]
+    fn short_ini() {
+        let model = parse(SHORT).unwrap();
+        assert!(model.model_table.is_none());
+        // Decimal values
+        assert_eq!(model.design_cap, 6400);
+        assert_eq!(model.i_chg_term, 819);
+        assert_eq!(model.learn_cfg, None);
+    }
+
+    #[test]
+    fn invalid_ini() {
+        assert_eq!(
+            parse(include_str!("../tests/fixtures/missing_key.ini")).unwrap_err(),
+            ParseError::MissingKey("TempCo")
+        );
+        assert_eq!(
+            parse(include_str!("../tests/fixtures/out_of_range.ini")).unwrap_err(),
+            ParseError::OutOfRange { line: 3 }
+        );
+        assert_eq!(
+            parse(include_str!("../tests/fixtures/incomplete_table.ini")).unwrap_err(),
+            ParseError::IncompleteModelTable { register: 0x9F }
+        );
+        assert_eq!(
+            parse("DesignCap = 0x12G4").unwrap_err(),
+            ParseError::InvalidValue { line: 1 }
+        );
+        assert_eq!(
+            parse("\nDesignCap").unwrap_err(),
+            ParseError::MalformedLine { line: 2 }
+        );
+        assert_eq!(
+            parse("RComp0 = 1\nrcomp0 = 2").unwrap_err(),
+            ParseError::DuplicateKey { line: 2 }
+        );
+        assert_eq!(
+            parse("0xB0 = 0x1234").unwrap_err(),
+            ParseError::OutOfRange { line: 1 }
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn load_file() {
+        let path = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/fixtures/lifepo4_full.ini"
+        );
+        assert_eq!(load(path).unwrap().design_cap, 0x1900);
+        assert!(matches!(
+            load(concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/tests/fixtures/missing.ini"
+            )),
+            Err(LoadError::Io(_))
+        ));
+    }
+}
@@ -0,0 +1,296 @@
+/// Learned ModelGauge m5 parameters - see steps 3.5 and 4 (page 8) of ModelGauge m5 Host Side
+/// Software Implementation Guide UG6595; Rev 4; 12/21
+///
+/// The gauge learns the cell's capacity and characteristics over time, and loses them on a POR.
+/// Save them periodically (e.g. whenever Cycles has changed) and restore them after the gauge
+/// has been configured following a POR.
+///
+/// `LearnedParameters::to_bytes` encodes the parameters as a versioned, CRC protected blob for
+/// storage in flash or EEPROM. With the `serde` feature they can also be serialized with serde.
+use crate::registers::Register;
+
+/// Delay before and after the capacity registers are restored, in milliseconds
+pub(crate) const RESTORE_DELAY_MS: u32 = 350;
+
+/// dPAcc value written on restore - 200% (LSB 1/16%)
+pub(crate) const D_P_ACC_RESTORE: u16 = 0x0C80;
+
+/// The QRTable registers, in the order of `LearnedParameters::qr_table`
+pub(crate) const QR_TABLE_REGISTERS: [u8; 4] = [
+    Register::QR_TABLE_00,
+    Register::QR_TABLE_10,
+    Register::QR_TABLE_20,
+    Register::QR_TABLE_30,
+];
+
+/// Identifies a learned parameter blob
+pub const BLOB_MAGIC: [u8; 2] = *b"M5";
+/// Version of the blob layout written by `LearnedParameters::to_bytes`
+pub const BLOB_VERSION: u8 = 1;
+/// Length of a version 1 blob in bytes
+pub const BLOB_LEN: usize = 24;
+
+/// Blob flag: the QRTable values are present
+const FLAG_QR_TABLE: u8 = 1 << 0;
+
+/// The learned parameters, as raw register values
+#[derive(Debug, Clone, Copy, PartialEq, Eq, defmt::Format)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LearnedParameters {
+    /// RComp0 Register (38h)
+    pub rcomp0: u16,
+    /// TempCo Register (39h)
+    pub temp_co: u16,
+    /// FullCapRep Register (10h)
+    pub full_cap_rep: u16,
+    /// Cycles Register (17h)
+    pub cycles: u16,
+    /// FullCapNom Register (23h)
+    pub full_cap_nom: u16,
+    /// QRTable00 to QRTable30 Registers (12h, 22h, 32h, 42h). `None` leaves the values loaded
+    /// with the model in place on restore.
+    pub qr_table: Option<[u16; 4]>,
+}
+
+/// Reasons a learned parameter blob is rejected
+#[derive(Debug, Clone, Copy, PartialEq, Eq, defmt::Format)]
+pub enum DecodeError {
+    /// The blob is not `BLOB_LEN` bytes long
+    /// * `0`: length of the blob
+    WrongLength(usize),
+    /// The blob does not start with `BLOB_MAGIC`
+    BadMagic,
+    /// The blob was written with a layout this version of the driver can't read
+    /// * `0`: version of the blob
+    UnsupportedVersion(u8),
+    /// The blob is corrupted
+    CrcMismatch,
+    /// The blob has flags set that this version of the driver doesn't know
+    /// * `0`: flags of the blob
+    UnknownFlags(u8),
+}
+
+impl LearnedParameters {
+    /// Encode as a blob for non-volatile storage.
+    ///
+    /// Layout (version 1), multi-byte values little endian:
+    /// * 0..2: `BLOB_MAGIC`
+    /// * 2: `BLOB_VERSION`
+    /// * 3: flags, bit 0 set if the QRTable values are present
+    /// * 4..14: RComp0, TempCo, FullCapRep, Cycles, FullCapNom
+    /// * 14..22: QRTable00 to QRTable30, zero if not present
+    /// * 22..24: CRC-16/CCITT-FALSE of bytes 0..22
+    pub fn to_bytes(&self) -> [u8; BLOB_LEN] {
+        let mut blob = [0u8; BLOB_LEN];
+        blob[0..2].copy_from_slice(&BLOB_MAGIC);
+        blob[2] = BLOB_VERSION;
+        blob[3] = if self.qr_table.is_some() {
+            FLAG_QR_TABLE
+        } else {
+            0
+        };
+        let words = [
+            self.rcomp0,
+            self.temp_co,
+            self.full_cap_rep,
+            self.cycles,
+            self.full_cap_nom,
+        ]
+        .into_iter()
+        .chain(self.qr_table.unwrap_or_default());
+        for (bytes, word) in blob[4..22].chunks_exact_mut(2).zip(words) {
+            bytes.copy_from_slice(&word.to_le_bytes());
+        }
+        let crc = crc16(&blob[..22]);
+        blob[22..].copy_from_slice(&crc.to_le_bytes());
+        blob
+    }
+
+    /// Decode a blob written by `to_bytes`, rejecting blobs that are corrupted or were written
+    /// with an unsupported layout
+    pub fn from_bytes(blob: &[u8]) -> Result<Self, DecodeError> {
+        if blob.len() < 3 {
+            return Err(DecodeError::WrongLength(blob.len()));
+        }
+        if blob[0..2] != BLOB_MAGIC {
+            return Err(DecodeError::BadMagic);
+        }
+        if blob[2] != BLOB_VERSION {
+            return Err(DecodeError::UnsupportedVersion(blob[2]));
+        }
+        if blob.len() != BLOB_LEN {
+            return Err(DecodeError::WrongLength(blob.len()));
+        }
+        if crc16(&blob[..22]) != u16::from_le_bytes([blob[22], blob[23]]) {
+            return Err(DecodeError::CrcMismatch);
+        }
+        let flags = blob[3];
+        if flags & !FLAG_QR_TABLE != 0 {
+            return Err(DecodeError::UnknownFlags(flags));
+        }
+        let word = |index: usize| u16::from_le_bytes([blob[4 + 2 * index], blob[5 + 2 * index]]);
+        Ok(Self {
+            rcomp0: word(0),
+            temp_co: word(1),
+            full_cap_rep: word(2),
+            cycles: word(3),
+            full_cap_nom: word(4),
+            qr_table: (flags & FLAG_QR_TABLE != 0).then(|| [word(5), word(6), word(7), word(8)]),
+        })
+    }
+
+    /// The registers restored before the 350ms wait (step 4.1), with their saved values
+    pub(crate) fn characterization_writes(&self) -> impl Iterator<Item = (u8, u16)> {
+        let qr_table = self
+            .qr_table
+            .map(|qr_table| QR_TABLE_REGISTERS.into_iter().zip(qr_table));
+        [
+            (Register::R_COMP_0, self.rcomp0),
+            (Register::TEMP_CO, self.temp_co),
+            (Register::FULL_CAP_NOM, self.full_cap_nom),
+        ]
+        .into_iter()
+        .chain(qr_table.into_iter().flatten())
+    }
+
+    /// The capacity register writes of step 4.3, given the present MixSOC register value
+    pub(crate) fn capacity_writes(&self, mix_soc: u16) -> [(u8, u16); 4] {
+        // MixCap = MixSOC x FullCapNom / 25600 (MixSOC LSB is 1/256%)
+        // and saturates, as MixSOC can read above 100%
+        let mix_cap = u32::from(mix_soc) * u32::from(self.full_cap_nom) / 25600;
+        let mix_cap = u16::try_from(mix_cap).unwrap_or(u16::MAX);
+        [
+            (Register::MIX_CAP, mix_cap),
+            (Register::FULL_CAP_REP, self.full_cap_rep),
+            // dPAcc = 200%, then dQAcc = 200% of capacity (LSB 16 x the capacity LSB)
+            (Register::D_P_ACC, D_P_ACC_RESTORE),
+            (Register::D_Q_ACC, self.full_cap_nom / 16),
+        ]
+    }
+}
+
+/// CRC-16/CCITT-FALSE: polynomial 0x1021, initial value 0xFFFF, no reflection
+fn crc16(data: &[u8]) -> u16 {
+    data.iter().fold(0xFFFF, |crc, &byte| {
+        (0..8).fold(crc ^ (u16::from(byte) << 8), |crc, _| {
+            if crc & 0x8000 != 0 {
+                (crc << 1) ^ 0x1021
+            } else {
+                crc << 1
+            }
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn learned() -> LearnedParameters {
+        LearnedParameters {
+            rcomp0: 0x0070,
+            temp_co: 0x223E,
+            full_cap_rep: 0x1300,
+            cycles: 0x0020,
+            full_cap_nom: 0x1400,
+            qr_table: Some([0x1050, 0x2012, 0x0B04, 0x0885]),
+        }
+    }
+
+    #[test]
+    fn capacity_writes() {
+        // 50% MixSOC
+        assert_eq!(
+            learned().capacity_writes(0x3200),
+            [
+                (Register::MIX_CAP, 0x0A00),
+                (Register::FULL_CAP_REP, 0x1300),
+                (Register::D_P_ACC, 0x0C80),
+                (Register::D_Q_ACC, 0x0140),
+            ]
+        );
+        // MixCap saturates rather than wrapping
+        let learned = LearnedParameters {
+            full_cap_nom: 0xFFFF,
+            ..learned()
+        };
+        assert_eq!(
+            learned.capacity_writes(0xFFFF)[0],
+            (Register::MIX_CAP, u16::MAX)
+        );
+    }
+
+    #[test]
+    fn crc() {
+        // CRC-16/CCITT-FALSE check value
+        assert_eq!(crc16(b"123456789"), 0x29B1);
+    }
+
+    #[test]
+    fn blob_round_trip() {
+        let blob = learned().to_bytes();
+        assert_eq!(&blob[..4], &[b'M', b'5', 1, 1]);
+        assert_eq!(&blob[4..6], &[0x70, 0x00]);
+        assert_eq!(LearnedParameters::from_bytes(&blob), Ok(learned()));
+
+        let without_qr_table = LearnedParameters {
+            qr_table: None,
+            ..learned()
+        };
+        let blob = without_qr_table.to_bytes();
+        assert_eq!(blob[3], 0);
+        assert_eq!(&blob[14..22], &[0; 8]);
+        assert_eq!(LearnedParameters::from_bytes(&blob), Ok(without_qr_table));
+    }
+
+    #[test]
+    fn bad_blobs_are_rejected() {
+        let blob = learned().to_bytes();
+        for index in 0..BLOB_LEN {
+            let mut corrupted = blob;
+            corrupted[index] ^= 0x10;
+            assert!(LearnedParameters::from_bytes(&corrupted).is_err());
+        }
+
+        let mut corrupted = blob;
+        corrupted[8] ^= 0x01;
+        assert_eq!(
+            LearnedParameters::from_bytes(&corrupted),
+            Err(DecodeError::CrcMismatch)
+        );
+        assert_eq!(
+            LearnedParameters::from_bytes(&blob[..BLOB_LEN - 1]),
+            Err(DecodeError::WrongLength(BLOB_LEN - 1))
+        );
+        assert_eq!(
+            LearnedParameters::from_bytes(&[0xFF; BLOB_LEN]),
+            Err(DecodeError::BadMagic)
+        );
+
+        let mut newer = blob;
+        newer[2] = 2;
+        assert_eq!(
+            LearnedParameters::from_bytes(&newer),
+            Err(DecodeError::UnsupportedVersion(2))
+        );
+
+        let mut unknown_flags = blob;
+        unknown_flags[3] |= 0x80;
+        let crc = crc16(&unknown_flags[..22]);
+        unknown_flags[22..].copy_from_slice(&crc.to_le_bytes());
+        assert_eq!(
+            LearnedParameters::from_bytes(&unknown_flags),
+            Err(DecodeError::UnknownFlags(0x81))
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn json_round_trip() {
+        let json = serde_json::to_string(&learned()).unwrap();
+        assert_eq!(
+            serde_json::from_str::<LearnedParameters>(&json).unwrap(),
+            learned()
+        );
+    }
+}
@@ -1,6 +1,21 @@
-#![cfg_attr(not(test), no_std)]
+#![cfg_attr(not(any(test, feature = "std")), no_std)]
+pub mod alert;
 pub mod comms;
+#[cfg(feature = "async")]
+pub mod comms_async;
 pub mod error;
+pub mod ini;
+pub mod learned;
+pub mod max17260;
+pub mod max17261;
+pub mod max17262;
 pub mod max17263;
+pub mod max17265;
+pub mod model;
+// modular-bitfield 0.11 wraps generated field types in parentheses, which trips this lint
+#[allow(unused_parens)]
 pub mod registers;
+#[cfg(any(test, feature = "simulator"))]
+pub mod simulator;
 pub mod traits;
+pub mod units;
@@ -0,0 +1,7 @@
+pub mod registers;
+
+use crate::comms::Max1726x;
+use registers::{Max17260RegisterResolver, Register};
+
+/// A MAX17260 driver
+pub type Max17260<I2C, R = Max17260RegisterResolver> = Max1726x<Register, I2C, R>;
@@ -0,0 +1,14 @@
+/// The register information in this module references:
+/// https://www.analog.com/media/en/technical-documentation/data-sheets/MAX17260.pdf
+/// MAX17260 datasheet
+///
+/// The MAX17260 is the single-cell base part of the family, with an external sense resistor. It
+/// has no registers beyond the family-wide ones in `crate::registers`.
+use crate::traits::model_register;
+
+/// The MAX17260 uses the same register LSBs as the MAX17263
+pub type Max17260RegisterResolver = crate::max17263::registers::Max17263RegisterResolver;
+/// Fixed-point variant of `Max17260RegisterResolver`, for parts without an FPU
+pub type Max17260FixedPointResolver = crate::max17263::registers::Max17263FixedPointResolver;
+
+model_register!("MAX17260");
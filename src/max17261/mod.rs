@@ -0,0 +1,7 @@
+pub mod registers;
+
+use crate::comms::Max1726x;
+use registers::{Max17261RegisterResolver, Register};
+
+/// A MAX17261 driver
+pub type Max17261<I2C, R = Max17261RegisterResolver> = Max1726x<Register, I2C, R>;
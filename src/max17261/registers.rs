@@ -0,0 +1,36 @@
+/// The register information in this module references:
+/// https://www.analog.com/media/en/technical-documentation/data-sheets/MAX17261.pdf
+/// MAX17261 datasheet
+///
+/// The MAX17261 is the multi-cell MAX17260: PackCfg sets the number of cells in series and Batt
+/// reports the pack voltage. Unlike the MAX17263 it has no LED driver.
+use crate::traits::{model_register, MultiCell};
+
+/// PackCfg is shared by the multi-cell parts and lives in `crate::registers`
+pub use crate::registers::{PackCfg, ThermistorType};
+
+/// The MAX17261 uses the same register LSBs as the MAX17263
+pub type Max17261RegisterResolver = crate::max17263::registers::Max17263RegisterResolver;
+/// Fixed-point variant of `Max17261RegisterResolver`, for parts without an FPU
+pub type Max17261FixedPointResolver = crate::max17263::registers::Max17263FixedPointResolver;
+
+model_register!("MAX17261");
+
+impl MultiCell for Register {
+    const PACK_CFG: u8 = Register::PACK_CFG;
+    const BATT: u8 = Register::BATT;
+}
+
+/// The multi-cell registers are at the same addresses as on the MAX17263
+impl Register {
+    /// PackCfg Register (BDh)
+    /// Register Type: Special
+    /// The PackCfg register configures the number of cells in the pack, the enabled temperature
+    /// channels and the thermistor type.
+    pub const PACK_CFG: u8 = crate::max17263::registers::Register::PACK_CFG;
+
+    /// Batt Register (DAh)
+    /// Register Type: Voltage (LSB 1.25mV)
+    /// The Batt register reports the voltage measured at the BATT pin, i.e. the total pack voltage.
+    pub const BATT: u8 = crate::max17263::registers::Register::BATT;
+}
@@ -0,0 +1,7 @@
+pub mod registers;
+
+use crate::comms::Max1726x;
+use registers::{Max17262RegisterResolver, Register};
+
+/// A MAX17262 driver
+pub type Max17262<I2C, R = Max17262RegisterResolver> = Max1726x<Register, I2C, R>;
@@ -0,0 +1,352 @@
+/// The register information in this module references:
+/// https://www.analog.com/media/en/technical-documentation/data-sheets/MAX17262.pdf
+/// MAX17262 datasheet
+///
+/// The MAX17262 is a single-cell fuel gauge with an internal sense resistor, so it has its own
+/// register resolver with fixed capacity and current LSBs. Otherwise its registers match the
+/// MAX17260.
+use core::num::NonZeroU32;
+
+use crate::max17263::registers::{Max17263FixedPointResolver, Max17263RegisterResolver};
+use crate::traits::{model_register, RegisterResolver};
+use crate::units::fixed;
+use crate::units::{
+    Amps, Celsius, FixedPoint, Float, MilliAmpHours, Ohms, Percent, Seconds, Volts, Watts,
+};
+
+/// The external sense resistor that gives a MAX17263 the same LSBs as the internal one, in µΩ
+const EQUIVALENT_R_SENSE: NonZeroU32 = NonZeroU32::new(10_000).unwrap();
+
+/// Register resolver for the MAX17262. The internal sense resistor gives the LSBs of a MAX17263
+/// with a 10mΩ sense resistor (0.5mAh, 156.25µA, 0.8mW), so this converts like
+/// `Max17263RegisterResolver` at 10mΩ.
+#[derive(Debug, Clone, Copy)]
+pub struct Max17262RegisterResolver(Max17263RegisterResolver);
+
+impl Max17262RegisterResolver {
+    /// Initialise the register resolver
+    pub const fn new() -> Self {
+        // EQUIVALENT_R_SENSE in ohms
+        Self(Max17263RegisterResolver::new(Ohms(0.010)))
+    }
+}
+
+impl Default for Max17262RegisterResolver {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RegisterResolver for Max17262RegisterResolver {
+    type Units = Float;
+
+    /// Capacity register to milliamp-hours
+    /// LSb size: 0.5mAh. Min value: 0.0mAh. Max value: 32.7675Ah.
+    fn register_to_capacity(&self, register: u16) -> MilliAmpHours {
+        self.0.register_to_capacity(register)
+    }
+
+    /// Percentage register to percentage
+    /// LSb size: 1/256%
+    fn register_to_percentage(&self, register: u16) -> Percent {
+        self.0.register_to_percentage(register)
+    }
+
+    /// Voltage register to volts
+    /// LSb size: 78.125µV
+    fn register_to_voltage(&self, register: u16) -> Volts {
+        self.0.register_to_voltage(register)
+    }
+
+    /// Current register to amps
+    /// LSb size: 156.25µA. Min value: -5.12A. Max value: 5.1198A.
+    /// Notes: Signed 2's complement format.
+    fn register_to_current(&self, register: u16) -> Amps {
+        self.0.register_to_current(register)
+    }
+
+    /// Temperature register to degrees celsius
+    /// LSb size: 1/256°C
+    fn register_to_temperature(&self, register: u16) -> Celsius {
+        self.0.register_to_temperature(register)
+    }
+
+    /// Resistance register to ohms
+    /// LSb size: 1/4096Ω
+    fn register_to_resistance(&self, register: u16) -> Ohms {
+        self.0.register_to_resistance(register)
+    }
+
+    /// Time register to seconds
+    /// LSb size: 5.625s
+    fn register_to_time(&self, register: u16) -> Seconds {
+        self.0.register_to_time(register)
+    }
+
+    /// Convert milliamp-hours to capacity register value
+    /// LSb size: 0.5mAh
+    fn capacity_to_register(&self, capacity: MilliAmpHours) -> u16 {
+        self.0.capacity_to_register(capacity)
+    }
+
+    /// Convert percentage to register value
+    /// LSb size: 1/256%
+    fn percentage_to_register(&self, percentage: Percent) -> u16 {
+        self.0.percentage_to_register(percentage)
+    }
+
+    /// Convert volts to register value
+    /// LSb size: 78.125µV
+    fn voltage_to_register(&self, voltage: Volts) -> u16 {
+        self.0.voltage_to_register(voltage)
+    }
+
+    /// Convert amps to current register value
+    /// LSb size: 156.25µA
+    fn current_to_register(&self, current: Amps) -> u16 {
+        self.0.current_to_register(current)
+    }
+
+    /// Convert celsius to temperature register value
+    /// LSb size: 1/256°C
+    fn temperature_to_register(&self, temperature: Celsius) -> u16 {
+        self.0.temperature_to_register(temperature)
+    }
+
+    /// Convert ohms to resistance register value
+    /// LSb size: 1/4096Ω
+    fn resistance_to_register(&self, resistance: Ohms) -> u16 {
+        self.0.resistance_to_register(resistance)
+    }
+
+    /// Convert seconds to time register value
+    /// LSb size: 5.625s
+    fn time_to_register(&self, seconds: Seconds) -> u16 {
+        self.0.time_to_register(seconds)
+    }
+
+    /// Power register to watts
+    /// LSb size: 0.8mW. Min value: -26.2144W. Max value: 26.2136W.
+    /// Notes: Signed 2's complement format.
+    fn register_to_power(&self, register: u16) -> Watts {
+        self.0.register_to_power(register)
+    }
+
+    /// Convert watts to power register value
+    /// LSb size: 0.8mW
+    fn power_to_register(&self, power: Watts) -> u16 {
+        self.0.power_to_register(power)
+    }
+
+    /// Pack voltage register to volts
+    /// LSb size: 1.25mV
+    fn register_to_pack_voltage(&self, register: u16) -> Volts {
+        self.0.register_to_pack_voltage(register)
+    }
+
+    /// Convert volts to pack voltage register value
+    /// LSb size: 1.25mV
+    fn pack_voltage_to_register(&self, voltage: Volts) -> u16 {
+        self.0.pack_voltage_to_register(voltage)
+    }
+
+    /// Cycles register to number of cycles
+    /// LSb size: 16%
+    fn register_to_cycles(&self, register: u16) -> f64 {
+        self.0.register_to_cycles(register)
+    }
+}
+
+/// Fixed-point register resolver for the MAX17262, for parts without an FPU. The internal sense
+/// resistor gives the LSBs of a MAX17263 with a 10mΩ sense resistor (0.5mAh, 156.25µA, 0.8mW),
+/// so this converts like `Max17263FixedPointResolver` at 10mΩ.
+#[derive(Debug, Clone, Copy)]
+pub struct Max17262FixedPointResolver(Max17263FixedPointResolver);
+
+impl Max17262FixedPointResolver {
+    /// Initialise the register resolver
+    pub const fn new() -> Self {
+        Self(Max17263FixedPointResolver::from_non_zero(
+            EQUIVALENT_R_SENSE,
+        ))
+    }
+}
+
+impl Default for Max17262FixedPointResolver {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RegisterResolver for Max17262FixedPointResolver {
+    type Units = FixedPoint;
+
+    /// Capacity register to milliamp-hours
+    /// LSb size: 0.5mAh
+    fn register_to_capacity(&self, register: u16) -> fixed::MilliAmpHours {
+        self.0.register_to_capacity(register)
+    }
+
+    /// Percentage register to thousandths of a percent
+    /// LSb size: 1/256%
+    fn register_to_percentage(&self, register: u16) -> fixed::MilliPercent {
+        self.0.register_to_percentage(register)
+    }
+
+    /// Voltage register to millivolts
+    /// LSb size: 78.125µV
+    fn register_to_voltage(&self, register: u16) -> fixed::MilliVolts {
+        self.0.register_to_voltage(register)
+    }
+
+    /// Current register to microamps
+    /// LSb size: 156.25µA
+    /// Notes: Signed 2's complement format.
+    fn register_to_current(&self, register: u16) -> fixed::MicroAmps {
+        self.0.register_to_current(register)
+    }
+
+    /// Temperature register to hundredths of a degree celsius
+    /// LSb size: 1/256°C
+    /// Notes: Signed 2's complement format.
+    fn register_to_temperature(&self, register: u16) -> fixed::CentiCelsius {
+        self.0.register_to_temperature(register)
+    }
+
+    /// Resistance register to milliohms
+    /// LSb size: 1/4096Ω
+    fn register_to_resistance(&self, register: u16) -> fixed::MilliOhms {
+        self.0.register_to_resistance(register)
+    }
+
+    /// Time register to milliseconds
+    /// LSb size: 5.625s
+    fn register_to_time(&self, register: u16) -> fixed::MilliSeconds {
+        self.0.register_to_time(register)
+    }
+
+    /// Convert milliamp-hours to capacity register value
+    /// LSb size: 0.5mAh
+    fn capacity_to_register(&self, capacity: fixed::MilliAmpHours) -> u16 {
+        self.0.capacity_to_register(capacity)
+    }
+
+    /// Convert thousandths of a percent to register value
+    /// LSb size: 1/256%
+    fn percentage_to_register(&self, percentage: fixed::MilliPercent) -> u16 {
+        self.0.percentage_to_register(percentage)
+    }
+
+    /// Convert millivolts to register value
+    /// LSb size: 78.125µV
+    fn voltage_to_register(&self, voltage: fixed::MilliVolts) -> u16 {
+        self.0.voltage_to_register(voltage)
+    }
+
+    /// Convert microamps to current register value
+    /// LSb size: 156.25µA
+    fn current_to_register(&self, current: fixed::MicroAmps) -> u16 {
+        self.0.current_to_register(current)
+    }
+
+    /// Convert hundredths of a degree celsius to temperature register value
+    /// LSb size: 1/256°C
+    fn temperature_to_register(&self, temperature: fixed::CentiCelsius) -> u16 {
+        self.0.temperature_to_register(temperature)
+    }
+
+    /// Convert milliohms to resistance register value
+    /// LSb size: 1/4096Ω
+    fn resistance_to_register(&self, resistance: fixed::MilliOhms) -> u16 {
+        self.0.resistance_to_register(resistance)
+    }
+
+    /// Convert milliseconds to time register value
+    /// LSb size: 5.625s
+    fn time_to_register(&self, time: fixed::MilliSeconds) -> u16 {
+        self.0.time_to_register(time)
+    }
+
+    /// Power register to milliwatts
+    /// LSb size: 0.8mW
+    /// Notes: Signed 2's complement format.
+    fn register_to_power(&self, register: u16) -> fixed::MilliWatts {
+        self.0.register_to_power(register)
+    }
+
+    /// Convert milliwatts to power register value
+    /// LSb size: 0.8mW
+    fn power_to_register(&self, power: fixed::MilliWatts) -> u16 {
+        self.0.power_to_register(power)
+    }
+
+    /// Pack voltage register to millivolts
+    /// LSb size: 1.25mV
+    fn register_to_pack_voltage(&self, register: u16) -> fixed::MilliVolts {
+        self.0.register_to_pack_voltage(register)
+    }
+
+    /// Convert millivolts to pack voltage register value
+    /// LSb size: 1.25mV
+    fn pack_voltage_to_register(&self, voltage: fixed::MilliVolts) -> u16 {
+        self.0.pack_voltage_to_register(voltage)
+    }
+
+    /// Cycles register to hundredths of a cycle
+    /// LSb size: 16%
+    fn register_to_cycles(&self, register: u16) -> fixed::CentiCycles {
+        self.0.register_to_cycles(register)
+    }
+}
+
+model_register!("MAX17262");
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::comms::{EzConfig, Max1726x};
+    use crate::max17262::Max17262;
+    use crate::registers::{Register as Max1726xRegister, VEmpty};
+    use crate::simulator::{Max1726xSimulator, SimClock};
+
+    #[test]
+    fn fixed_point_matches_float() {
+        use crate::max17263::registers::tests::{
+            assert_register_to_value_matches, assert_value_to_register_matches,
+        };
+        let float = Max17262RegisterResolver::new();
+        let fixed = Max17262FixedPointResolver::new();
+        assert_register_to_value_matches(&float, &fixed);
+        assert_value_to_register_matches(&float, &fixed, EQUIVALENT_R_SENSE.get());
+    }
+
+    #[test]
+    fn fixed_lsbs() {
+        let resolver = Max17262RegisterResolver::new();
+        assert_eq!(resolver.register_to_capacity(5000), MilliAmpHours(2500.0));
+        assert_eq!(resolver.capacity_to_register(MilliAmpHours(2500.0)), 5000);
+        assert!((resolver.register_to_current(i16::MIN as u16).0 + 5.12).abs() < 1e-9);
+        assert_eq!(resolver.current_to_register(Amps(-0.15625)), 0xFC18);
+        assert_eq!(resolver.power_to_register(Watts(-0.8)), 0xFC18);
+    }
+
+    #[test]
+    fn ez_config_scales_with_internal_sense_resistor() {
+        let clock = SimClock::new();
+        let mut gauge: Max17262<_> = Max1726x::new(
+            Max1726xSimulator::new(&clock),
+            Max17262RegisterResolver::new(),
+        );
+        let ez_config = EzConfig {
+            charge_voltage: Volts(4.2),
+            design_cap: MilliAmpHours(2500.0),
+            i_chg_term: Amps(0.1),
+            v_empty: VEmpty::init(3300, 3880),
+        };
+        gauge.ez_config(clock.delay(), ez_config).unwrap();
+        let simulator = gauge.destroy();
+        // 0.5mAh and 156.25µA LSBs
+        assert_eq!(simulator.register(Max1726xRegister::DESIGN_CAP), 5000);
+        assert_eq!(simulator.register(Max1726xRegister::I_CHG_TERM), 640);
+    }
+}
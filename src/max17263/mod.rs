@@ -1 +1,85 @@
+// modular-bitfield 0.11 wraps generated field types in parentheses, which trips this lint
+#[allow(unused_parens)]
 pub mod registers;
+
+use crate::{comms::Max1726x, error::Error, traits::RegisterResolver};
+use embedded_hal::i2c;
+use registers::{LedCfg1, LedCfg2, LedCfg3, Max17263RegisterResolver, Register};
+
+/// A MAX17263 driver
+pub type Max17263<I2C, R = Max17263RegisterResolver> = Max1726x<Register, I2C, R>;
+
+/// The LED driver is only available on the MAX17263, so these methods don't exist for other parts:
+///
+/// ```
+/// # use embedded_hal::i2c::{ErrorKind, I2c};
+/// # fn leds<I2C: I2c<Error = ErrorKind>>(gauge: &mut max1726x::max17263::Max17263<I2C>) {
+/// let led_cfg_1 = gauge.led_cfg_1_register();
+/// # }
+/// ```
+///
+/// ```compile_fail
+/// # use embedded_hal::i2c::{ErrorKind, I2c};
+/// # fn leds<I2C: I2c<Error = ErrorKind>>(gauge: &mut max1726x::max17260::Max17260<I2C>) {
+/// let led_cfg_1 = gauge.led_cfg_1_register();
+/// # }
+/// ```
+impl<I2C, E, R> Max1726x<Register, I2C, R>
+where
+    I2C: i2c::I2c<Error = E>,
+    E: i2c::Error,
+    R: RegisterResolver,
+{
+    /// Get LEDCfg1 Register (40h)
+    pub fn led_cfg_1_register(&mut self) -> Result<LedCfg1, Error<E>> {
+        self.read_register_as_bitfield::<LedCfg1>()
+    }
+
+    /// Get LEDCfg2 Register (4Bh)
+    pub fn led_cfg_2_register(&mut self) -> Result<LedCfg2, Error<E>> {
+        self.read_register_as_bitfield::<LedCfg2>()
+    }
+
+    /// Get LEDCfg3 Register (37h)
+    pub fn led_cfg_3_register(&mut self) -> Result<LedCfg3, Error<E>> {
+        self.read_register_as_bitfield::<LedCfg3>()
+    }
+
+    /// Configure the LED driver (LEDCfg1, LEDCfg2 and LEDCfg3)
+    pub fn configure_leds(
+        &mut self,
+        led_cfg_1: LedCfg1,
+        led_cfg_2: LedCfg2,
+        led_cfg_3: LedCfg3,
+    ) -> Result<(), Error<E>> {
+        self.write_bitfield_to_register(led_cfg_1)?;
+        self.write_bitfield_to_register(led_cfg_2)?;
+        self.write_bitfield_to_register(led_cfg_3)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simulator::{Max1726xSimulator, SimClock};
+    use crate::units::Ohms;
+
+    #[test]
+    fn configure_leds() {
+        let clock = SimClock::new();
+        let mut gauge: Max17263<_> = Max1726x::new(
+            Max1726xSimulator::new(&clock),
+            Max17263RegisterResolver::new(Ohms(0.010)),
+        );
+        gauge
+            .configure_leds(
+                LedCfg1::new().with_n_bars(5).with_led_md(1),
+                LedCfg2::from(0x011F),
+                LedCfg3::new().with_full_spd(true),
+            )
+            .unwrap();
+        assert_eq!(gauge.led_cfg_1_register().unwrap().n_bars(), 5);
+        assert_eq!(u16::from(gauge.led_cfg_2_register().unwrap()), 0x011F);
+        assert!(gauge.led_cfg_3_register().unwrap().full_spd());
+    }
+}
@@ -1,9 +1,17 @@
 /// The register information in this module references:
 /// https://www.analog.com/media/en/technical-documentation/data-sheets/MAX17263.pdf
 /// MAX17263 datasheet
+use core::num::NonZeroU32;
 use modular_bitfield::prelude::*;
 
-use crate::traits::{BitField, Model, RegisterResolver};
+use crate::traits::{BitField, Model, MultiCell, RegisterResolver};
+use crate::units::fixed::{self, div_round, MicroOhms};
+use crate::units::{
+    Amps, Celsius, FixedPoint, Float, MilliAmpHours, Ohms, Percent, Seconds, Volts, Watts,
+};
+
+/// PackCfg is shared by the multi-cell parts and lives in `crate::registers`
+pub use crate::registers::{PackCfg, ThermistorType};
 
 #[derive(Debug, Clone, Copy)]
 pub struct Max17263RegisterResolver {
@@ -11,100 +19,313 @@ pub struct Max17263RegisterResolver {
 }
 impl Max17263RegisterResolver {
     /// Initialise the register resolver.
-    /// * `r_sense` - The sense resistor value (ohms)
-    pub const fn new(r_sense: f64) -> Self {
-        Self { r_sense }
+    /// * `r_sense` - The sense resistor value
+    pub const fn new(r_sense: Ohms) -> Self {
+        Self { r_sense: r_sense.0 }
     }
 }
 
 impl RegisterResolver for Max17263RegisterResolver {
-    /// Capacity register to amp-hours
+    type Units = Float;
+
+    /// Capacity register to milliamp-hours
     /// LSb size: 5.0µVh / RSENSE. Min value: 0.0µVh. Max value: 327.675mVh / RSENSE.
-    /// Notes: Equivalent to 0.5mAh with a 0.010Ω sense resistor.
-    fn register_to_capacity(&self, register: u16) -> f64 {
-        register as f64 * 5.0e-6 / self.r_sense
+    /// Notes: Equivalent to 0.5mAh with a 0.010Ω sense resistor.
+    fn register_to_capacity(&self, register: u16) -> MilliAmpHours {
+        MilliAmpHours(register as f64 * 5.0e-3 / self.r_sense)
     }
 
     /// Percentage register to percentage
     /// LSb SIZE: 1/256%. Min value: 0.0%. Max value: 255.9961%.
     /// Notes: 1% LSb when reading only the upper byte.
-    fn register_to_percentage(&self, register: u16) -> f64 {
-        register as f64 / 256.0
+    fn register_to_percentage(&self, register: u16) -> Percent {
+        Percent(register as f64 / 256.0)
     }
 
     /// Voltage register to volts
     /// LSb size: 78.125uV. Min value: 0.0V. Max value: 5.11992V.
     /// Notes: On per-cell basis.
-    fn register_to_voltage(&self, register: u16) -> f64 {
-        register as f64 * 78.125e-6
+    fn register_to_voltage(&self, register: u16) -> Volts {
+        Volts(register as f64 * 78.125e-6)
     }
 
     /// Current register to amps
     /// LSb size: 1.5625uV / RSENSE. Min value: -51.2mV / RSENSE. Max value: 51.1984mV / RSENSE.
-    /// Notes: Signed 2's complement format. Equivalent to 156.25µA with a 0.010Ω sense resistor.
-    fn register_to_current(&self, register: u16) -> f64 {
-        (register as i16) as f64 * 1.5625e-6 / self.r_sense
+    /// Notes: Signed 2's complement format. Equivalent to 156.25µA with a 0.010Ω sense resistor.
+    fn register_to_current(&self, register: u16) -> Amps {
+        Amps((register as i16) as f64 * 1.5625e-6 / self.r_sense)
     }
 
     /// Temperature register to degrees celsius
     /// LSb size: 1/256°C. Min value: -128°C. Max value: 127.996°C.
     /// Notes: Signed 2's complement format. 1°C LSb when reading only the upper byte.
-    fn register_to_temperature(&self, register: u16) -> f64 {
-        (register as i16) as f64 / 256.0
+    fn register_to_temperature(&self, register: u16) -> Celsius {
+        Celsius((register as i16) as f64 / 256.0)
     }
 
     /// Resistance register to ohms
-    /// LSb size: 1/4096Ω. Min value: 0Ω. Max value: 15.99976Ω.
-    fn register_to_resistance(&self, register: u16) -> f64 {
-        register as f64 / 4096.0
+    /// LSb size: 1/4096Ω. Min value: 0Ω. Max value: 15.99976Ω.
+    fn register_to_resistance(&self, register: u16) -> Ohms {
+        Ohms(register as f64 / 4096.0)
     }
 
     /// Time register to seconds
     /// LSb size: 5.625s. Min value: 0s. Max value: 102.3984h.
-    fn register_to_time(&self, register: u16) -> f64 {
-        register as f64 * 5.625
+    fn register_to_time(&self, register: u16) -> Seconds {
+        Seconds(register as f64 * 5.625)
     }
 
-    /// Convert amp-hours to capacity register value
+    /// Convert milliamp-hours to capacity register value
     /// LSb size: 5.0µVh / RSENSE
-    fn capacity_to_register(&self, amp_hours: f64) -> u16 {
-        libm::round((amp_hours * self.r_sense) / 5.0e-6) as u16
+    fn capacity_to_register(&self, capacity: MilliAmpHours) -> u16 {
+        libm::round((capacity.0 * self.r_sense) / 5.0e-3) as u16
     }
 
     /// Convert percentage to register value
     /// LSb size: 1/256%
-    fn percentage_to_register(&self, percentage: f64) -> u16 {
-        libm::round(percentage * 256.0) as u16
+    fn percentage_to_register(&self, percentage: Percent) -> u16 {
+        libm::round(percentage.0 * 256.0) as u16
     }
 
     /// Convert volts to register value
     /// LSb size: 78.125uV
-    fn voltage_to_register(&self, volts: f64) -> u16 {
-        libm::round(volts / 78.125e-6) as u16
+    fn voltage_to_register(&self, voltage: Volts) -> u16 {
+        libm::round(voltage.0 / 78.125e-6) as u16
     }
 
     /// Convert amps to current register value
     /// LSb size: 1.5625uV / RSENSE
-    fn current_to_register(&self, amps: f64) -> u16 {
-        libm::round((amps * self.r_sense) / 1.5625e-6) as i16 as u16
+    fn current_to_register(&self, current: Amps) -> u16 {
+        libm::round((current.0 * self.r_sense) / 1.5625e-6) as i16 as u16
     }
 
     /// Convert celsius to temperature register value
     /// LSb size: 1/256°C
-    fn temperature_to_register(&self, celsius: f64) -> u16 {
-        libm::round(celsius * 256.0) as i16 as u16
+    fn temperature_to_register(&self, temperature: Celsius) -> u16 {
+        libm::round(temperature.0 * 256.0) as i16 as u16
     }
 
     /// Convert ohms to resistance register value
     /// LSb size: 1/4096Ω
-    fn resistance_to_register(&self, ohms: f64) -> u16 {
-        libm::round(ohms * 4096.0) as u16
+    fn resistance_to_register(&self, resistance: Ohms) -> u16 {
+        libm::round(resistance.0 * 4096.0) as u16
     }
 
     /// Convert seconds to time register value
     /// LSb size: 5.625s
-    fn time_to_register(&self, seconds: f64) -> u16 {
-        libm::round(seconds / 5.625) as u16
+    fn time_to_register(&self, seconds: Seconds) -> u16 {
+        libm::round(seconds.0 / 5.625) as u16
+    }
+
+    /// Power register to watts
+    /// LSb size: 8.0µV² / RSENSE. Min value: -262.144mV² / RSENSE. Max value: 262.136mV² / RSENSE.
+    /// Notes: Signed 2's complement format. Equivalent to 0.8mW with a 0.010Ω sense resistor.
+    fn register_to_power(&self, register: u16) -> Watts {
+        Watts((register as i16) as f64 * 8.0e-6 / self.r_sense)
+    }
+
+    /// Convert watts to power register value
+    /// LSb size: 8.0µV² / RSENSE
+    fn power_to_register(&self, power: Watts) -> u16 {
+        libm::round((power.0 * self.r_sense) / 8.0e-6) as i16 as u16
+    }
+
+    /// Pack voltage register to volts
+    /// LSb size: 1.25mV. Min value: 0.0V. Max value: 81.92V.
+    /// Notes: Total pack voltage in multi-cell applications.
+    fn register_to_pack_voltage(&self, register: u16) -> Volts {
+        Volts(register as f64 * 1.25e-3)
+    }
+
+    /// Convert volts to pack voltage register value
+    /// LSb size: 1.25mV
+    fn pack_voltage_to_register(&self, voltage: Volts) -> u16 {
+        libm::round(voltage.0 / 1.25e-3) as u16
+    }
+
+    /// Cycles register to number of cycles
+    /// LSb size: 16%. Min value: 0 cycles. Max value: 10485.6 cycles.
+    fn register_to_cycles(&self, register: u16) -> f64 {
+        f64::from(register) * 0.16
+    }
+}
+
+/// Register resolver using integer fixed-point arithmetic, for parts without an FPU. With this
+/// resolver the driver doesn't use floating point at all.
+///
+/// Values are rounded to the nearest unit of the `units::fixed` quantities and saturate at the
+/// register limits, like `Max17263RegisterResolver`. Currents saturate at the `i32` range, which
+/// full scale exceeds with a sense resistor below 24µΩ.
+#[derive(Debug, Clone, Copy)]
+pub struct Max17263FixedPointResolver {
+    r_sense_uohm: i64,
+}
+
+impl Max17263FixedPointResolver {
+    /// Initialise the register resolver. Returns `None` for a 0µΩ sense resistor, which the
+    /// capacity, current and power conversions would divide by.
+    /// * `r_sense` - The sense resistor value
+    pub const fn new(r_sense: MicroOhms) -> Option<Self> {
+        match NonZeroU32::new(r_sense.0) {
+            Some(r_sense) => Some(Self::from_non_zero(r_sense)),
+            None => None,
+        }
+    }
+
+    /// Initialise the register resolver from a sense resistor value in µΩ already known to be
+    /// non-zero
+    pub(crate) const fn from_non_zero(r_sense_uohm: NonZeroU32) -> Self {
+        Self {
+            r_sense_uohm: r_sense_uohm.get() as i64,
+        }
+    }
+}
+
+/// Saturate an unsigned register value
+fn unsigned_register(value: i64) -> u16 {
+    value.clamp(0, i64::from(u16::MAX)) as u16
+}
+
+/// Saturate a two's-complement register value
+fn signed_register(value: i64) -> u16 {
+    value.clamp(i64::from(i16::MIN), i64::from(i16::MAX)) as i16 as u16
+}
+
+/// Saturate a signed value to the `i32` range. Full-scale current overflows it below 24µΩ.
+fn signed_value(value: i64) -> i32 {
+    value.clamp(i64::from(i32::MIN), i64::from(i32::MAX)) as i32
+}
+
+impl RegisterResolver for Max17263FixedPointResolver {
+    type Units = FixedPoint;
+
+    /// Capacity register to milliamp-hours
+    /// LSb size: 5.0µVh / RSENSE, i.e. 5000 / RSENSE(µΩ) mAh
+    fn register_to_capacity(&self, register: u16) -> fixed::MilliAmpHours {
+        fixed::MilliAmpHours(div_round(i64::from(register) * 5000, self.r_sense_uohm) as u32)
+    }
+
+    /// Percentage register to thousandths of a percent
+    /// LSb size: 1/256%, i.e. 125/32 m%
+    fn register_to_percentage(&self, register: u16) -> fixed::MilliPercent {
+        fixed::MilliPercent(div_round(i64::from(register) * 125, 32) as u32)
+    }
+
+    /// Voltage register to millivolts
+    /// LSb size: 78.125µV, i.e. 5/64 mV
+    fn register_to_voltage(&self, register: u16) -> fixed::MilliVolts {
+        fixed::MilliVolts(div_round(i64::from(register) * 5, 64) as u32)
+    }
+
+    /// Current register to microamps
+    /// LSb size: 1.5625µV / RSENSE, i.e. 1562500 / RSENSE(µΩ) µA
+    /// Notes: Signed 2's complement format.
+    fn register_to_current(&self, register: u16) -> fixed::MicroAmps {
+        fixed::MicroAmps(signed_value(div_round(
+            i64::from(register as i16) * 1_562_500,
+            self.r_sense_uohm,
+        )))
+    }
+
+    /// Temperature register to hundredths of a degree celsius
+    /// LSb size: 1/256°C, i.e. 25/64 c°C
+    /// Notes: Signed 2's complement format.
+    fn register_to_temperature(&self, register: u16) -> fixed::CentiCelsius {
+        fixed::CentiCelsius(div_round(i64::from(register as i16) * 25, 64) as i32)
+    }
+
+    /// Resistance register to milliohms
+    /// LSb size: 1/4096Ω, i.e. 125/512 mΩ
+    fn register_to_resistance(&self, register: u16) -> fixed::MilliOhms {
+        fixed::MilliOhms(div_round(i64::from(register) * 125, 512) as u32)
+    }
+
+    /// Time register to milliseconds
+    /// LSb size: 5.625s, i.e. 5625ms
+    fn register_to_time(&self, register: u16) -> fixed::MilliSeconds {
+        fixed::MilliSeconds(u32::from(register) * 5625)
+    }
+
+    /// Convert milliamp-hours to capacity register value
+    /// LSb size: 5.0µVh / RSENSE
+    fn capacity_to_register(&self, capacity: fixed::MilliAmpHours) -> u16 {
+        unsigned_register(div_round(
+            i64::from(capacity.0) * self.r_sense_uohm,
+            5000,
+        ))
+    }
+
+    /// Convert thousandths of a percent to register value
+    /// LSb size: 1/256%
+    fn percentage_to_register(&self, percentage: fixed::MilliPercent) -> u16 {
+        unsigned_register(div_round(i64::from(percentage.0) * 32, 125))
+    }
+
+    /// Convert millivolts to register value
+    /// LSb size: 78.125µV
+    fn voltage_to_register(&self, voltage: fixed::MilliVolts) -> u16 {
+        unsigned_register(div_round(i64::from(voltage.0) * 64, 5))
+    }
+
+    /// Convert microamps to current register value
+    /// LSb size: 1.5625µV / RSENSE
+    fn current_to_register(&self, current: fixed::MicroAmps) -> u16 {
+        signed_register(div_round(
+            i64::from(current.0) * self.r_sense_uohm,
+            1_562_500,
+        ))
+    }
+
+    /// Convert hundredths of a degree celsius to temperature register value
+    /// LSb size: 1/256°C
+    fn temperature_to_register(&self, temperature: fixed::CentiCelsius) -> u16 {
+        signed_register(div_round(i64::from(temperature.0) * 64, 25))
+    }
+
+    /// Convert milliohms to resistance register value
+    /// LSb size: 1/4096Ω
+    fn resistance_to_register(&self, resistance: fixed::MilliOhms) -> u16 {
+        unsigned_register(div_round(i64::from(resistance.0) * 512, 125))
+    }
+
+    /// Convert milliseconds to time register value
+    /// LSb size: 5.625s
+    fn time_to_register(&self, time: fixed::MilliSeconds) -> u16 {
+        unsigned_register(div_round(i64::from(time.0), 5625))
+    }
+
+    /// Power register to milliwatts
+    /// LSb size: 8.0µV² / RSENSE, i.e. 8000 / RSENSE(µΩ) mW
+    /// Notes: Signed 2's complement format.
+    fn register_to_power(&self, register: u16) -> fixed::MilliWatts {
+        fixed::MilliWatts(signed_value(div_round(
+            i64::from(register as i16) * 8000,
+            self.r_sense_uohm,
+        )))
+    }
+
+    /// Convert milliwatts to power register value
+    /// LSb size: 8.0µV² / RSENSE
+    fn power_to_register(&self, power: fixed::MilliWatts) -> u16 {
+        signed_register(div_round(i64::from(power.0) * self.r_sense_uohm, 8000))
+    }
+
+    /// Pack voltage register to millivolts
+    /// LSb size: 1.25mV, i.e. 5/4 mV
+    fn register_to_pack_voltage(&self, register: u16) -> fixed::MilliVolts {
+        fixed::MilliVolts(div_round(i64::from(register) * 5, 4) as u32)
+    }
+
+    /// Convert millivolts to pack voltage register value
+    /// LSb size: 1.25mV
+    fn pack_voltage_to_register(&self, voltage: fixed::MilliVolts) -> u16 {
+        unsigned_register(div_round(i64::from(voltage.0) * 4, 5))
+    }
+
+    /// Cycles register to hundredths of a cycle
+    /// LSb size: 16%
+    fn register_to_cycles(&self, register: u16) -> fixed::CentiCycles {
+        fixed::CentiCycles(u32::from(register) * 16)
     }
 }
 
@@ -131,8 +352,31 @@ impl Model for Register {
     /// The Temp register provides the temperature measured by the thermistor or die temperature based on the Config register
     /// setting.
     const TEMP: u8 = 0x08;
+
+    const AGE_FORECAST: Option<u8> = Some(Register::AGE_FORECAST);
+}
+impl MultiCell for Register {
+    const PACK_CFG: u8 = Register::PACK_CFG;
+    const BATT: u8 = Register::BATT;
 }
 impl Register {
+    /// PackCfg Register (BDh)
+    /// Register Type: Special
+    /// The PackCfg register configures the number of cells in the pack, the enabled temperature
+    /// channels and the thermistor type.
+    pub const PACK_CFG: u8 = 0xBD;
+
+    /// Batt Register (DAh)
+    /// Register Type: Voltage (LSB 1.25mV)
+    /// The Batt register reports the voltage measured at the BATT pin, i.e. the total pack voltage.
+    pub const BATT: u8 = 0xDA;
+
+    /// AgeForecast Register (B9h)
+    /// Register Type: Special (LSB 16% of a cycle)
+    /// The AgeForecast register reports the estimated total cycle life of the cell. Compare it to
+    /// the Cycles register to estimate the remaining cycle life.
+    pub const AGE_FORECAST: u8 = 0xB9;
+
     /// LEDCfg1 Register (40h) (page 29)
     /// Initial value: 0x6070
     /// The LEDCfg1 register configures the LED driver operation. If any LED activity is initiated, the MAX17263 automatically
@@ -329,30 +573,21 @@ impl BitField for FullCapRep {
 }
 
 impl FullCapRep {
-    /// Convert the register value to amp-hours
-    pub fn to_amp_hours(&self, r_sense: f32) -> f32 {
-        self.capacity() as f32 * 5.0e-6 / r_sense
-    }
-
-    /// Convert the register value to milliamp-hours
-    pub fn to_milliamp_hours(&self, r_sense: f32) -> f32 {
-        self.to_amp_hours(r_sense) * 1000.0
+    /// Convert the register value to milliamp-hours with the sense resistor `r_sense`
+    pub fn to_milliamp_hours(&self, r_sense: Ohms) -> MilliAmpHours {
+        Max17263RegisterResolver::new(r_sense).register_to_capacity(self.capacity())
     }
 
-    /// Create a new FullCapRep register with the specified capacity in milliamp-hours
-    pub fn from_milliamp_hours(milliamp_hours: f32, r_sense: f32) -> Self {
-        let capacity = ((milliamp_hours * r_sense) / (5.0e-3)) as u16;
-        Self::new().with_capacity(capacity)
+    /// Create a new FullCapRep register with the specified capacity, rounded to the nearest LSB
+    pub fn from_milliamp_hours(capacity: MilliAmpHours, r_sense: Ohms) -> Self {
+        let resolver = Max17263RegisterResolver::new(r_sense);
+        Self::new().with_capacity(resolver.capacity_to_register(capacity))
     }
 }
 
 impl defmt::Format for FullCapRep {
     fn format(&self, f: defmt::Formatter) {
-        defmt::write!(
-            f,
-            "FullCapRep: raw: {}",
-            self.capacity(),
-        )
+        defmt::write!(f, "FullCapRep: raw: {}", self.capacity(),)
     }
 }
 
@@ -374,35 +609,24 @@ impl BitField for AvgVCell {
 
 impl AvgVCell {
     /// Convert the register value to volts
-    pub fn to_volts(&self) -> f32 {
-        self.voltage() as f32 * 78.125e-6
+    pub fn to_volts(&self) -> Volts {
+        Volts(f64::from(self.voltage()) * 78.125e-6)
     }
 
-    /// Convert the register value to millivolts
-    pub fn to_millivolts(&self) -> f32 {
-        self.to_volts() * 1000.0
-    }
-
-    /// Create a new AvgVCell register with the specified voltage in millivolts
-    pub fn from_millivolts(millivolts: f32) -> Self {
-        let voltage = ((millivolts * 1000.0) / 78.125) as u16;
-        Self::new().with_voltage(voltage)
+    /// Create a new AvgVCell register with the specified voltage, rounded to the nearest LSB
+    pub fn from_volts(voltage: Volts) -> Self {
+        Self::new().with_voltage(libm::round(voltage.0 / 78.125e-6) as u16)
     }
 }
 
 impl defmt::Format for AvgVCell {
     fn format(&self, f: defmt::Formatter) {
-        defmt::write!(
-            f,
-            "AvgVCell: raw: {}, millivolts: {}",
-            self.voltage(),
-            self.to_millivolts()
-        )
+        defmt::write!(f, "AvgVCell: raw: {}, {}", self.voltage(), self.to_volts())
     }
 }
 
 #[cfg(test)]
-mod tests {
+pub(crate) mod tests {
     use super::*;
     #[test]
     fn led_cfg_1() {
@@ -441,178 +665,435 @@ mod tests {
 
     #[test]
     fn test_register_to_capacity() {
-        let resolver = Max17263RegisterResolver::new(0.010);
+        let resolver = Max17263RegisterResolver::new(Ohms(0.010));
 
-        assert_eq!(resolver.register_to_capacity(0x0000), 0.0);
+        assert_eq!(resolver.register_to_capacity(0x0000).0, 0.0);
         assert!(
-            (resolver.register_to_capacity(0xFFFF) - 327.675e-3 / resolver.r_sense).abs() < 1e-6
+            (resolver.register_to_capacity(0xFFFF).0 - 327.675 / resolver.r_sense).abs() < 1e-6
         );
     }
 
     #[test]
     fn test_register_to_percentage() {
-        let resolver = Max17263RegisterResolver::new(0.010);
+        let resolver = Max17263RegisterResolver::new(Ohms(0.010));
 
-        assert_eq!(resolver.register_to_percentage(0x0000), 0.0);
-        assert!((resolver.register_to_percentage(0xFFFF) - 255.9961).abs() < 1e-4);
+        assert_eq!(resolver.register_to_percentage(0x0000).0, 0.0);
+        assert!((resolver.register_to_percentage(0xFFFF).0 - 255.9961).abs() < 1e-4);
     }
 
     #[test]
     fn test_register_to_voltage() {
-        let resolver = Max17263RegisterResolver::new(0.010);
+        let resolver = Max17263RegisterResolver::new(Ohms(0.010));
 
-        assert_eq!(resolver.register_to_voltage(0x0000), 0.0);
-        assert!((resolver.register_to_voltage(0xFFFF) - 5.11992).abs() < 1e-5);
+        assert_eq!(resolver.register_to_voltage(0x0000).0, 0.0);
+        assert!((resolver.register_to_voltage(0xFFFF).0 - 5.11992).abs() < 1e-5);
     }
 
     #[test]
     fn test_register_to_current() {
-        let resolver = Max17263RegisterResolver::new(0.010);
+        let resolver = Max17263RegisterResolver::new(Ohms(0.010));
 
         assert!(
-            (resolver.register_to_current(i16::MIN as u16) + 51.2e-3 / resolver.r_sense).abs()
+            (resolver.register_to_current(i16::MIN as u16).0 + 51.2e-3 / resolver.r_sense).abs()
                 < 1e-5
         );
         assert!(
-            (resolver.register_to_current(i16::MAX as u16) - 51.1984e-3 / resolver.r_sense).abs()
+            (resolver.register_to_current(i16::MAX as u16).0 - 51.1984e-3 / resolver.r_sense).abs()
                 < 1e-5
         );
     }
 
     #[test]
     fn test_register_to_temperature() {
-        let resolver = Max17263RegisterResolver::new(0.010);
+        let resolver = Max17263RegisterResolver::new(Ohms(0.010));
 
-        assert_eq!(resolver.register_to_temperature(0x0000), 0.0);
-        assert!((resolver.register_to_temperature(0x7FFF) - 127.996).abs() < 1e-3);
-        assert!((resolver.register_to_temperature(0x8000) - -128.0).abs() < 1e-3);
+        assert_eq!(resolver.register_to_temperature(0x0000).0, 0.0);
+        assert!((resolver.register_to_temperature(0x7FFF).0 - 127.996).abs() < 1e-3);
+        assert!((resolver.register_to_temperature(0x8000).0 - -128.0).abs() < 1e-3);
     }
 
     #[test]
     fn test_register_to_resistance() {
-        let resolver = Max17263RegisterResolver::new(0.010);
+        let resolver = Max17263RegisterResolver::new(Ohms(0.010));
 
-        assert_eq!(resolver.register_to_resistance(0x0000), 0.0);
-        assert!((resolver.register_to_resistance(0xFFFF) - 15.99976).abs() < 1e-5);
+        assert_eq!(resolver.register_to_resistance(0x0000).0, 0.0);
+        assert!((resolver.register_to_resistance(0xFFFF).0 - 15.99976).abs() < 1e-5);
     }
 
     #[test]
     fn test_register_to_time() {
-        let resolver = Max17263RegisterResolver::new(0.010);
+        let resolver = Max17263RegisterResolver::new(Ohms(0.010));
 
-        assert_eq!(resolver.register_to_time(0x0000), 0.0);
-        assert!((resolver.register_to_time(0xFFFF) - 102.3984 * 3600.0).abs() < 1.0);
+        assert_eq!(resolver.register_to_time(0x0000).0, 0.0);
+        assert!((resolver.register_to_time(0xFFFF).0 - 102.3984 * 3600.0).abs() < 1.0);
     }
 
     #[test]
     fn full_cap_rep_conversion() {
-        let r_sense = 0.010; // 10mΩ
-        
+        let r_sense = Ohms(0.010); // 10mΩ
+
         // Test conversion with typical battery capacity (2000mAh)
-        let full_cap = FullCapRep::from_milliamp_hours(2000.0, r_sense as f32);
-        assert!((full_cap.to_milliamp_hours(r_sense as f32) - 2000.0).abs() < 1.0);
-        
+        let full_cap = FullCapRep::from_milliamp_hours(MilliAmpHours(2000.0), r_sense);
+        assert!((full_cap.to_milliamp_hours(r_sense).0 - 2000.0).abs() < 1.0);
+
         // Test roundtrip conversion
-        let test_capacity = 3000.0; // 3000mAh
-        let full_cap = FullCapRep::from_milliamp_hours(test_capacity, r_sense as f32);
-        let result = full_cap.to_milliamp_hours(r_sense as f32);
-        assert!((result - test_capacity).abs() < 1.0);
+        let test_capacity = MilliAmpHours(3000.0);
+        let full_cap = FullCapRep::from_milliamp_hours(test_capacity, r_sense);
+        let result = full_cap.to_milliamp_hours(r_sense);
+        assert!((result.0 - test_capacity.0).abs() < 1.0);
+
+        // Rounds to the nearest LSB: 2000.4mAh is 4000.8 LSBs of 0.5mAh
+        let full_cap = FullCapRep::from_milliamp_hours(MilliAmpHours(2000.4), r_sense);
+        assert_eq!(full_cap.capacity(), 4001);
     }
 
     #[test]
     fn avg_vcell_conversion() {
         // Test typical battery voltage (3.7V)
-        let avg_vcell = AvgVCell::from_millivolts(3700.0);
-        assert!((avg_vcell.to_millivolts() - 3700.0).abs() < 0.1);
+        let avg_vcell = AvgVCell::from_volts(Volts(3.7));
+        assert!((avg_vcell.to_volts().0 - 3.7).abs() < 1e-4);
 
         // Test roundtrip conversion
-        let test_voltage = 4200.0; // 4.2V
-        let avg_vcell = AvgVCell::from_millivolts(test_voltage);
-        let result = avg_vcell.to_millivolts();
-        assert!((result - test_voltage).abs() < 0.1);
+        let test_voltage = Volts(4.2);
+        let avg_vcell = AvgVCell::from_volts(test_voltage);
+        let result = avg_vcell.to_volts();
+        assert!((result.0 - test_voltage.0).abs() < 1e-4);
+
+        // Rounds to the nearest LSB: 3.70007V is 47360.9 LSBs
+        assert_eq!(AvgVCell::from_volts(Volts(3.70007)).voltage(), 47361);
     }
 
     #[test]
     fn test_capacity_roundtrip() {
-        let resolver = Max17263RegisterResolver::new(0.010);
-        let test_values = [0.0, 0.001, 0.1, 1.0, 10.0];
-        
+        let resolver = Max17263RegisterResolver::new(Ohms(0.010));
+        let test_values = [0.0, 1.0, 100.0, 1000.0, 10000.0];
+
         for &value in &test_values {
-            let register = resolver.capacity_to_register(value);
-            let result = resolver.register_to_capacity(register);
-            assert!((result - value).abs() < 1e-6, "Capacity roundtrip failed for {}", value);
+            let register = resolver.capacity_to_register(MilliAmpHours(value));
+            let result = resolver.register_to_capacity(register).0;
+            assert!(
+                (result - value).abs() < 1e-3,
+                "Capacity roundtrip failed for {}",
+                value
+            );
         }
     }
 
     #[test]
     fn test_percentage_roundtrip() {
-        let resolver = Max17263RegisterResolver::new(0.010);
+        let resolver = Max17263RegisterResolver::new(Ohms(0.010));
         let test_values = [0.0, 0.5, 50.0, 99.9, 255.0];
-        
+
         for &value in &test_values {
-            let register = resolver.percentage_to_register(value);
-            let result = resolver.register_to_percentage(register);
-            assert!((result - value).abs() < 0.01, "Percentage roundtrip failed for {}", value);
+            let register = resolver.percentage_to_register(Percent(value));
+            let result = resolver.register_to_percentage(register).0;
+            assert!(
+                (result - value).abs() < 0.01,
+                "Percentage roundtrip failed for {}",
+                value
+            );
         }
     }
 
     #[test]
     fn test_voltage_roundtrip() {
-        let resolver = Max17263RegisterResolver::new(0.010);
+        let resolver = Max17263RegisterResolver::new(Ohms(0.010));
         let test_values = [0.0, 1.0, 3.3, 3.7, 4.2];
-        
+
         for &value in &test_values {
-            let register = resolver.voltage_to_register(value);
-            let result = resolver.register_to_voltage(register);
-            assert!((result - value).abs() < 1e-4, "Voltage roundtrip failed for {}", value);
+            let register = resolver.voltage_to_register(Volts(value));
+            let result = resolver.register_to_voltage(register).0;
+            assert!(
+                (result - value).abs() < 1e-4,
+                "Voltage roundtrip failed for {}",
+                value
+            );
         }
     }
 
     #[test]
     fn test_current_roundtrip() {
-        let resolver = Max17263RegisterResolver::new(0.010);
+        let resolver = Max17263RegisterResolver::new(Ohms(0.010));
         let test_values = [-5.0, -1.0, 0.0, 1.0, 5.0];
-        
+
         for &value in &test_values {
-            let register = resolver.current_to_register(value);
-            let result = resolver.register_to_current(register);
-            assert!((result - value).abs() < 1e-4, "Current roundtrip failed for {}", value);
+            let register = resolver.current_to_register(Amps(value));
+            let result = resolver.register_to_current(register).0;
+            assert!(
+                (result - value).abs() < 1e-4,
+                "Current roundtrip failed for {}",
+                value
+            );
         }
     }
 
     #[test]
     fn test_temperature_roundtrip() {
-        let resolver = Max17263RegisterResolver::new(0.010);
+        let resolver = Max17263RegisterResolver::new(Ohms(0.010));
         let test_values = [-40.0, 0.0, 25.0, 45.0, 85.0];
-        
+
         for &value in &test_values {
-            let register = resolver.temperature_to_register(value);
-            let result = resolver.register_to_temperature(register);
-            assert!((result - value).abs() < 0.01, "Temperature roundtrip failed for {}", value);
+            let register = resolver.temperature_to_register(Celsius(value));
+            let result = resolver.register_to_temperature(register).0;
+            assert!(
+                (result - value).abs() < 0.01,
+                "Temperature roundtrip failed for {}",
+                value
+            );
         }
     }
 
     #[test]
     fn test_resistance_roundtrip() {
-        let resolver = Max17263RegisterResolver::new(0.010);
+        let resolver = Max17263RegisterResolver::new(Ohms(0.010));
         let test_values = [0.0, 0.01, 1.0, 10.0, 15.0];
-        
+
         for &value in &test_values {
-            let register = resolver.resistance_to_register(value);
-            let result = resolver.register_to_resistance(register);
-            assert!((result - value).abs() < 1e-4, "Resistance roundtrip failed for {}", value);
+            let register = resolver.resistance_to_register(Ohms(value));
+            let result = resolver.register_to_resistance(register).0;
+            assert!(
+                (result - value).abs() < 1e-4,
+                "Resistance roundtrip failed for {}",
+                value
+            );
         }
     }
 
     #[test]
     fn test_time_roundtrip() {
-        let resolver = Max17263RegisterResolver::new(0.010);
+        let resolver = Max17263RegisterResolver::new(Ohms(0.010));
         let test_values = [0.0, 60.0, 3600.0, 86400.0];
-        
+
         for &value in &test_values {
-            let register = resolver.time_to_register(value);
-            let result = resolver.register_to_time(register);
-            assert!((result - value).abs() < 6.0, "Time roundtrip failed for {}", value);
+            let register = resolver.time_to_register(Seconds(value));
+            let result = resolver.register_to_time(register).0;
+            assert!(
+                (result - value).abs() < 6.0,
+                "Time roundtrip failed for {}",
+                value
+            );
         }
     }
+
+    /// Sense resistors to check the fixed-point resolver with, in µΩ
+    const FIXED_POINT_R_SENSE: [u32; 3] = [5_000, 10_000, 20_000];
+
+    /// Assert that a fixed-point value is the float value, scaled and rounded to the nearest unit
+    fn assert_rounded(fixed: i64, float: f64, scale: f64, what: &str, register: u16) {
+        assert!(
+            (fixed as f64 - float * scale).abs() <= 0.5 + 1e-6,
+            "{what} {register:#06X}: fixed {fixed}, float {float}"
+        );
+    }
+
+    /// Assert that `fixed` converts every register value to the value `float` converts it to,
+    /// rounded to the nearest unit
+    pub(crate) fn assert_register_to_value_matches<F, X>(float: &F, fixed: &X)
+    where
+        F: RegisterResolver<Units = Float>,
+        X: RegisterResolver<Units = FixedPoint>,
+    {
+        for register in 0..=u16::MAX {
+            assert_rounded(
+                fixed.register_to_capacity(register).0.into(),
+                float.register_to_capacity(register).0,
+                1.0,
+                "capacity",
+                register,
+            );
+            assert_rounded(
+                fixed.register_to_percentage(register).0.into(),
+                float.register_to_percentage(register).0,
+                1e3,
+                "percentage",
+                register,
+            );
+            assert_rounded(
+                fixed.register_to_voltage(register).0.into(),
+                float.register_to_voltage(register).0,
+                1e3,
+                "voltage",
+                register,
+            );
+            assert_rounded(
+                fixed.register_to_current(register).0.into(),
+                float.register_to_current(register).0,
+                1e6,
+                "current",
+                register,
+            );
+            assert_rounded(
+                fixed.register_to_temperature(register).0.into(),
+                float.register_to_temperature(register).0,
+                1e2,
+                "temperature",
+                register,
+            );
+            assert_rounded(
+                fixed.register_to_resistance(register).0.into(),
+                float.register_to_resistance(register).0,
+                1e3,
+                "resistance",
+                register,
+            );
+            assert_rounded(
+                fixed.register_to_time(register).0.into(),
+                float.register_to_time(register).0,
+                1e3,
+                "time",
+                register,
+            );
+            assert_rounded(
+                fixed.register_to_power(register).0.into(),
+                float.register_to_power(register).0,
+                1e3,
+                "power",
+                register,
+            );
+            assert_rounded(
+                fixed.register_to_pack_voltage(register).0.into(),
+                float.register_to_pack_voltage(register).0,
+                1e3,
+                "pack voltage",
+                register,
+            );
+            assert_rounded(
+                fixed.register_to_cycles(register).0.into(),
+                float.register_to_cycles(register),
+                1e2,
+                "cycles",
+                register,
+            );
+        }
+    }
+
+    /// Assert that `fixed` converts values to the same register values as `float`. `r_sense` is
+    /// the sense resistor in µΩ, or the one the LSBs are equivalent to.
+    pub(crate) fn assert_value_to_register_matches<F, X>(float: &F, fixed: &X, r_sense: u32)
+    where
+        F: RegisterResolver<Units = Float>,
+        X: RegisterResolver<Units = FixedPoint>,
+    {
+        for value in 0..=20_000u32 {
+            let signed = value as i32 - 10_000;
+            assert_eq!(
+                fixed.capacity_to_register(fixed::MilliAmpHours(value)),
+                float.capacity_to_register(MilliAmpHours(f64::from(value))),
+                "capacity {value}mAh"
+            );
+            assert_eq!(
+                fixed.percentage_to_register(fixed::MilliPercent(value * 10)),
+                float.percentage_to_register(Percent(f64::from(value) / 1e2)),
+                "percentage {value}0m%"
+            );
+            assert_eq!(
+                fixed.voltage_to_register(fixed::MilliVolts(value / 4)),
+                float.voltage_to_register(Volts(f64::from(value / 4) / 1e3)),
+                "voltage {}mV",
+                value / 4
+            );
+            assert_eq!(
+                fixed.current_to_register(fixed::MicroAmps(signed * 100)),
+                float.current_to_register(Amps(f64::from(signed) / 1e4)),
+                "current {signed}00µA"
+            );
+            assert_eq!(
+                fixed.temperature_to_register(fixed::CentiCelsius(signed)),
+                float.temperature_to_register(Celsius(f64::from(signed) / 1e2)),
+                "temperature {signed}c°C"
+            );
+            assert_eq!(
+                fixed.resistance_to_register(fixed::MilliOhms(value / 2)),
+                float.resistance_to_register(Ohms(f64::from(value / 2) / 1e3)),
+                "resistance {}mΩ",
+                value / 2
+            );
+            assert_eq!(
+                fixed.time_to_register(fixed::MilliSeconds(value * 1000)),
+                float.time_to_register(Seconds(f64::from(value))),
+                "time {value}s"
+            );
+            // Exact halves of the power LSB round differently in floating point
+            if (i64::from(signed) * i64::from(r_sense)).abs() % 8000 != 4000 {
+                assert_eq!(
+                    fixed.power_to_register(fixed::MilliWatts(signed)),
+                    float.power_to_register(Watts(f64::from(signed) / 1e3)),
+                    "power {signed}mW"
+                );
+            }
+            assert_eq!(
+                fixed.pack_voltage_to_register(fixed::MilliVolts(value * 4)),
+                float.pack_voltage_to_register(Volts(f64::from(value * 4) / 1e3)),
+                "pack voltage {}mV",
+                value * 4
+            );
+        }
+    }
+
+    #[test]
+    fn fixed_point_register_to_value_matches_float() {
+        for r_sense in FIXED_POINT_R_SENSE {
+            assert_register_to_value_matches(
+                &Max17263RegisterResolver::new(Ohms(f64::from(r_sense) * 1e-6)),
+                &Max17263FixedPointResolver::new(MicroOhms(r_sense)).unwrap(),
+            );
+        }
+    }
+
+    #[test]
+    fn fixed_point_value_to_register_matches_float() {
+        for r_sense in FIXED_POINT_R_SENSE {
+            assert_value_to_register_matches(
+                &Max17263RegisterResolver::new(Ohms(f64::from(r_sense) * 1e-6)),
+                &Max17263FixedPointResolver::new(MicroOhms(r_sense)).unwrap(),
+                r_sense,
+            );
+        }
+    }
+
+    #[test]
+    fn fixed_point_saturates() {
+        let fixed = Max17263FixedPointResolver::new(MicroOhms(10_000)).unwrap();
+        assert_eq!(fixed.voltage_to_register(fixed::MilliVolts(6000)), u16::MAX);
+        assert_eq!(
+            fixed.current_to_register(fixed::MicroAmps(-10_000_000)),
+            0x8000
+        );
+        assert_eq!(
+            fixed.current_to_register(fixed::MicroAmps(10_000_000)),
+            0x7FFF
+        );
+        assert_eq!(
+            fixed.temperature_to_register(fixed::CentiCelsius(-30_000)),
+            0x8000
+        );
+    }
+
+    #[test]
+    fn fixed_point_saturates_small_sense_resistor() {
+        let fixed = Max17263FixedPointResolver::new(MicroOhms(23)).unwrap();
+        assert_eq!(fixed.register_to_current(0x7FFF), fixed::MicroAmps(i32::MAX));
+        assert_eq!(fixed.register_to_current(0x8000), fixed::MicroAmps(i32::MIN));
+
+        let fixed = Max17263FixedPointResolver::new(MicroOhms(24)).unwrap();
+        assert_eq!(
+            fixed.register_to_current(0x7FFF),
+            fixed::MicroAmps(2_133_268_229)
+        );
+        assert_eq!(
+            fixed.register_to_current(0x8000),
+            fixed::MicroAmps(-2_133_333_333)
+        );
+
+        let fixed = Max17263FixedPointResolver::new(MicroOhms(1)).unwrap();
+        assert_eq!(fixed.register_to_current(0x7FFF), fixed::MicroAmps(i32::MAX));
+        assert_eq!(fixed.register_to_power(0x7FFF), fixed::MilliWatts(262_136_000));
+        assert_eq!(fixed.register_to_power(0x8000), fixed::MilliWatts(-262_144_000));
+    }
+
+    #[test]
+    fn fixed_point_rejects_zero_sense_resistor() {
+        assert!(Max17263FixedPointResolver::new(MicroOhms(0)).is_none());
+        assert!(Max17263FixedPointResolver::new(MicroOhms(1)).is_some());
+    }
 }
@@ -0,0 +1,7 @@
+pub mod registers;
+
+use crate::comms::Max1726x;
+use registers::{Max17265RegisterResolver, Register};
+
+/// A MAX17265 driver
+pub type Max17265<I2C, R = Max17265RegisterResolver> = Max1726x<Register, I2C, R>;
@@ -0,0 +1,14 @@
+/// The register information in this module references:
+/// https://www.analog.com/media/en/technical-documentation/data-sheets/MAX17265.pdf
+/// MAX17265 datasheet
+///
+/// The MAX17265 is a single-cell fuel gauge with an external sense resistor. Its register map
+/// matches the MAX17260, so only the part name differs in the driver.
+use crate::traits::model_register;
+
+/// The MAX17265 uses the same register LSBs as the MAX17263
+pub type Max17265RegisterResolver = crate::max17263::registers::Max17263RegisterResolver;
+/// Fixed-point variant of `Max17265RegisterResolver`, for parts without an FPU
+pub type Max17265FixedPointResolver = crate::max17263::registers::Max17263FixedPointResolver;
+
+model_register!("MAX17265");
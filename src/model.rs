@@ -0,0 +1,108 @@
+/// Custom characterized models - see "2.2 OPTION 2 Custom Short INI" and "2.3 OPTION 3 Custom
+/// Full INI" (page 7) of ModelGauge m5 Host Side Software Implementation Guide UG6595; Rev 4; 12/21
+///
+/// A custom model replaces the EZ configuration when the cell is not covered by the EZ models,
+/// e.g. LiFePO4 (ModelCfg.ModelID = 6). The values come from the INI file of the cell's
+/// characterization, as raw register values.
+use crate::registers::{ModelCfg, Register, VEmpty};
+
+/// Number of words in the model table (80h to AFh)
+pub const MODEL_TABLE_LEN: usize = 48;
+
+/// Model access unlock values for `Register::MODEL_UNLOCK_1` and `Register::MODEL_UNLOCK_2`
+pub(crate) const MODEL_UNLOCK: [(u8, u16); 2] = [
+    (Register::MODEL_UNLOCK_1, 0x0059),
+    (Register::MODEL_UNLOCK_2, 0x00C4),
+];
+
+/// Model access lock values for `Register::MODEL_UNLOCK_1` and `Register::MODEL_UNLOCK_2`
+pub(crate) const MODEL_LOCK: [(u8, u16); 2] = [
+    (Register::MODEL_UNLOCK_1, 0x0000),
+    (Register::MODEL_UNLOCK_2, 0x0000),
+];
+
+/// A custom ModelGauge m5 characterization
+#[derive(Debug, Clone, defmt::Format)]
+pub struct CustomModel {
+    /// Model table (80h to AFh). Provided by a full INI (option 3), `None` for a short INI
+    /// (option 2).
+    pub model_table: Option<[u16; MODEL_TABLE_LEN]>,
+    /// DesignCap Register (18h)
+    pub design_cap: u16,
+    /// IChgTerm Register (1Eh)
+    pub i_chg_term: u16,
+    /// VEmpty Register (3Ah)
+    pub v_empty: VEmpty,
+    /// ModelCfg Register (DBh). Refresh is set by the driver when the model is loaded.
+    pub model_cfg: ModelCfg,
+    /// RComp0 Register (38h)
+    pub rcomp0: u16,
+    /// TempCo Register (39h)
+    pub temp_co: u16,
+    /// QRTable00 to QRTable30 Registers (12h, 22h, 32h, 42h)
+    pub qr_table: [u16; 4],
+    /// LearnCfg Register (28h), `None` to keep the power-up value
+    pub learn_cfg: Option<u16>,
+    /// FilterCfg Register (29h), `None` to keep the power-up value
+    pub filter_cfg: Option<u16>,
+    /// RelaxCfg Register (2Ah), `None` to keep the power-up value
+    pub relax_cfg: Option<u16>,
+    /// MiscCfg Register (2Bh), `None` to keep the power-up value
+    pub misc_cfg: Option<u16>,
+    /// FullSOCThr Register (13h), `None` to keep the power-up value
+    pub full_soc_thr: Option<u16>,
+}
+
+impl CustomModel {
+    /// The register writes of the model parameters, in the order they are made after the model
+    /// table has been loaded. ModelCfg is written separately, last.
+    ///
+    /// dQAcc and dPAcc are seeded as in the guide: dQAcc = DesignCap / 32 and dPAcc = dQAcc x
+    /// 44138 / DesignCap, or dQAcc x 51200 / DesignCap with ModelCfg.VChg set.
+    pub(crate) fn parameter_writes(&self) -> impl Iterator<Item = (u8, u16)> {
+        let d_q_acc = self.design_cap / 32;
+        let d_p_acc_scale: u32 = if self.model_cfg.v_chg() { 51200 } else { 44138 };
+        let d_p_acc = (u32::from(d_q_acc) * d_p_acc_scale)
+            .checked_div(u32::from(self.design_cap))
+            .unwrap_or(0);
+        let d_p_acc = u16::try_from(d_p_acc).unwrap_or(u16::MAX);
+        let optional = [
+            (Register::LEARN_CFG, self.learn_cfg),
+            (Register::FILTER_CFG, self.filter_cfg),
+            (Register::RELAX_CFG, self.relax_cfg),
+            (Register::MISC_CFG, self.misc_cfg),
+            (Register::FULL_SOC_THR, self.full_soc_thr),
+        ];
+        [
+            (Register::DESIGN_CAP, self.design_cap),
+            (Register::D_Q_ACC, d_q_acc),
+            (Register::I_CHG_TERM, self.i_chg_term),
+            (Register::V_EMPTY, u16::from(self.v_empty)),
+            (Register::D_P_ACC, d_p_acc),
+        ]
+        .into_iter()
+        .chain(
+            optional
+                .into_iter()
+                .filter_map(|(register, data)| data.map(|data| (register, data))),
+        )
+    }
+
+    /// The register writes made once ModelCfg.Refresh has cleared: RComp0, TempCo and the
+    /// QRTable. Writing them earlier would be overwritten by the model refresh.
+    pub(crate) fn post_refresh_writes(&self) -> [(u8, u16); 6] {
+        [
+            (Register::R_COMP_0, self.rcomp0),
+            (Register::TEMP_CO, self.temp_co),
+            (Register::QR_TABLE_00, self.qr_table[0]),
+            (Register::QR_TABLE_10, self.qr_table[1]),
+            (Register::QR_TABLE_20, self.qr_table[2]),
+            (Register::QR_TABLE_30, self.qr_table[3]),
+        ]
+    }
+
+    /// The ModelCfg value that loads the model
+    pub(crate) fn model_cfg_refresh(&self) -> u16 {
+        self.model_cfg.with_refresh(true).into()
+    }
+}
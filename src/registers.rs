@@ -4,6 +4,8 @@
 use modular_bitfield::prelude::*;
 
 use crate::traits::BitField;
+use crate::units::{Ohms, Volts};
+use core::time::Duration;
 
 pub struct Register;
 impl Register {
@@ -18,6 +20,11 @@ impl Register {
     /// The FStat register is a read-only register that monitors the status of the ModelGauge m5
     /// algorithm. Table 20 is the FStat register format.
     pub const F_STAT: u8 = 0x3D;
+    /// Status2 Register (B0h)
+    /// Register Type: Special
+    /// The Status2 register reports the hibernate state and full detection. Neither flag asserts the
+    /// ALRT pin.
+    pub const STATUS_2: u8 = 0xB0;
     /// HibCfg Register (BAh)
     /// Register Type: Special
     /// Initial Value: 0x870C
@@ -81,6 +88,39 @@ impl Register {
     /// the voltage fuel gauge. This value is used in other internal calculations
     pub const V_FOCV: u8 = 0xFB;
 
+    /// Age Register (07h)
+    /// Register Type: Percentage
+    /// The Age register contains a calculated percentage value of the application's present
+    /// cell capacity compared to its expected capacity, i.e. FullCapRep / DesignCap.
+    pub const AGE: u8 = 0x07;
+
+    /// AvgCurrent Register (0Bh)
+    /// Register Type: Current
+    /// The AvgCurrent register reports an average of Current register readings.
+    pub const AVG_CURRENT: u8 = 0x0B;
+
+    /// AvgTA Register (16h)
+    /// Register Type: Temperature
+    /// The AvgTA register reports an average of the readings from the Temp register.
+    pub const AVG_TA: u8 = 0x16;
+
+    /// Power Register (B1h)
+    /// Register Type: Special
+    /// Instant power calculation from the immediate current and voltage. LSB = 8μV²/RSENSE
+    pub const POWER: u8 = 0xB1;
+
+    /// AvgPower Register (B3h)
+    /// Register Type: Special
+    /// Filtered average power from the Power register, with the time constant set by
+    /// Config2.POWR. LSB = 8μV²/RSENSE
+    pub const AVG_POWER: u8 = 0xB3;
+
+    /// AvgVCell Register (19h)
+    /// Register Type: Voltage
+    /// The AvgVCell register reports an average of the VCell register readings. In multi-cell
+    /// applications this is the average per-cell voltage of the pack.
+    pub const AVG_V_CELL: u8 = 0x19;
+
     /// TTF Register (20h)
     /// Register Type: Time
     /// The TTF register holds the estimated time to full for the application under present conditions.
@@ -96,6 +136,179 @@ impl Register {
     /// The corresponding AvgCurrent filtering gives a delay in TTE, but provides more stable results.
     /// The LSB of the TTE register is 5.625s.
     pub const TTE: u8 = 0x11;
+
+    /// Config Register (1Dh) (page 35)
+    /// Register Type: Special
+    /// Initial Value: 0x2210
+    /// The Config register holds all shutdown enable, alert enable, and temperature enable control
+    /// bits. Writing a bit location enables the corresponding function within one task period.
+    pub const CONFIG: u8 = 0x1D;
+
+    /// Config2 Register (BBh) (page 36)
+    /// Register Type: Special
+    /// Initial Value: 0x3658
+    /// The Config2 register holds additional alert enables, the firmware restart command and the
+    /// power averaging configuration.
+    pub const CONFIG_2: u8 = 0xBB;
+
+    /// VAlrtTh Register (01h) (page 33)
+    /// Register Type: Special
+    /// Initial Value: 0xFF00 (Disabled)
+    /// The VAlrtTh register sets upper and lower limits that generate an alert if exceeded by the
+    /// VCell register value.
+    pub const V_ALRT_TH: u8 = 0x01;
+
+    /// TAlrtTh Register (02h) (page 33)
+    /// Register Type: Special
+    /// Initial Value: 0x7F80 (Disabled)
+    /// The TAlrtTh register sets upper and lower limits that generate an alert if exceeded by the
+    /// Temp register value.
+    pub const T_ALRT_TH: u8 = 0x02;
+
+    /// SAlrtTh Register (03h) (page 34)
+    /// Register Type: Special
+    /// Initial Value: 0xFF00 (Disabled)
+    /// The SAlrtTh register sets upper and lower limits that generate an alert if exceeded by
+    /// RepSOC.
+    pub const S_ALRT_TH: u8 = 0x03;
+
+    /// IAlrtTh Register (B4h) (page 34)
+    /// Register Type: Special
+    /// Initial Value: 0x7F80 (Disabled)
+    /// The IAlrtTh register sets upper and lower limits that generate an alert if exceeded by the
+    /// Current register value.
+    pub const I_ALRT_TH: u8 = 0xB4;
+
+    /// MaxMinVolt Register (1Bh)
+    /// Register Type: Special
+    /// Initial Value: 0x00FF
+    /// The MaxMinVolt register maintains the maximum and minimum of VCell register values since
+    /// device reset.
+    pub const MAX_MIN_VOLT: u8 = 0x1B;
+
+    /// MaxMinCurr Register (1Ch)
+    /// Register Type: Special
+    /// Initial Value: 0x807F
+    /// The MaxMinCurr register maintains the maximum and minimum of Current register values since
+    /// device reset.
+    pub const MAX_MIN_CURR: u8 = 0x1C;
+
+    /// MaxMinTemp Register (1Ah)
+    /// Register Type: Special
+    /// Initial Value: 0x807F
+    /// The MaxMinTemp register maintains the maximum and minimum Temp register values since device
+    /// reset.
+    pub const MAX_MIN_TEMP: u8 = 0x1A;
+
+    /// FullCapRep Register (10h) (page 30)
+    /// Register Type: Capacity
+    /// This register reports the full capacity that goes with RepCap, generally used for reporting
+    /// to the GUI. Learned parameter, see `LearnedParameters`.
+    pub const FULL_CAP_REP: u8 = 0x10;
+
+    /// Cycles Register (17h) (page 31)
+    /// Register Type: Special
+    /// The Cycles register maintains a total count of the number of charge/discharge cycles of
+    /// the cell that have occurred. The LSB is 16%. Learned parameter, see `LearnedParameters`.
+    pub const CYCLES: u8 = 0x17;
+
+    /// FullCapNom Register (23h) (page 30)
+    /// Register Type: Capacity
+    /// This register holds the calculated full capacity of the cell, not including temperature
+    /// and empty compensation. Learned parameter, see `LearnedParameters`.
+    pub const FULL_CAP_NOM: u8 = 0x23;
+
+    /// RComp0 Register (38h) (page 38)
+    /// Register Type: Special
+    /// The RComp0 register holds characterization information critical to computing the
+    /// open-circuit voltage of a cell under loaded conditions. Learned parameter, see
+    /// `LearnedParameters`.
+    pub const R_COMP_0: u8 = 0x38;
+
+    /// TempCo Register (39h) (page 38)
+    /// Register Type: Special
+    /// The TempCo register holds temperature compensation information for the RComp0 register
+    /// value. Learned parameter, see `LearnedParameters`.
+    pub const TEMP_CO: u8 = 0x39;
+
+    /// MixSOC Register (0Dh)
+    /// Register Type: Percentage
+    /// The MixSOC register holds the calculated present state of charge of the cell before any
+    /// empty compensation adjustments are performed.
+    pub const MIX_SOC: u8 = 0x0D;
+
+    /// MixCap Register (0Fh)
+    /// Register Type: Capacity
+    /// The MixCap register holds the calculated remaining capacity of the cell before any empty
+    /// compensation adjustments are performed.
+    pub const MIX_CAP: u8 = 0x0F;
+
+    /// dQAcc Register (45h)
+    /// Register Type: Capacity (LSB 16 x the capacity LSB)
+    /// The dQAcc register tracks the change in battery charge between relaxation points. It is
+    /// available to the user for debug purposes.
+    pub const D_Q_ACC: u8 = 0x45;
+
+    /// dPAcc Register (46h)
+    /// Register Type: Percentage (LSB 1/16%)
+    /// The dPAcc register tracks the change in battery state of charge between relaxation
+    /// points. It is available to the user for debug purposes.
+    pub const D_P_ACC: u8 = 0x46;
+
+    /// QRTable00 Register (12h)
+    /// Register Type: Special
+    /// The QRTable00 to QRTable30 register locations contain characterization information
+    /// regarding cell capacity under different application conditions.
+    pub const QR_TABLE_00: u8 = 0x12;
+    /// QRTable10 Register (22h), see `QR_TABLE_00`
+    pub const QR_TABLE_10: u8 = 0x22;
+    /// QRTable20 Register (32h), see `QR_TABLE_00`
+    pub const QR_TABLE_20: u8 = 0x32;
+    /// QRTable30 Register (42h), see `QR_TABLE_00`
+    pub const QR_TABLE_30: u8 = 0x42;
+
+    /// FullSOCThr Register (13h)
+    /// Register Type: Percentage
+    /// Initial Value: 0x5005 (80%)
+    /// The FullSOCThr register gates detection of end-of-charge. VFSOC must be larger than the
+    /// FullSOCThr value before IChgTerm is compared to the AvgCurrent register value.
+    pub const FULL_SOC_THR: u8 = 0x13;
+
+    /// LearnCfg Register (28h)
+    /// Register Type: Special
+    /// The LearnCfg register controls the learning of the cell characterization. Part of a
+    /// custom model, see `CustomModel`.
+    pub const LEARN_CFG: u8 = 0x28;
+
+    /// FilterCfg Register (29h)
+    /// Register Type: Special
+    /// The FilterCfg register sets the averaging time period for all ADC readings, mixing
+    /// algorithm and empty compensation. Part of a custom model, see `CustomModel`.
+    pub const FILTER_CFG: u8 = 0x29;
+
+    /// RelaxCfg Register (2Ah)
+    /// Register Type: Special
+    /// The RelaxCfg register defines how the IC detects if the cell is in a relaxed state. Part
+    /// of a custom model, see `CustomModel`.
+    pub const RELAX_CFG: u8 = 0x2A;
+
+    /// MiscCfg Register (2Bh)
+    /// Register Type: Special
+    /// The MiscCfg register controls miscellaneous settings of the fuel gauge. Part of a custom
+    /// model, see `CustomModel`.
+    pub const MISC_CFG: u8 = 0x2B;
+
+    /// Model access unlock registers (62h and 63h)
+    /// Write 0x0059 to 62h and 0x00C4 to 63h to unlock access to the model table, and 0x0000 to
+    /// both to lock it again. While locked, the model table reads as 0x0000.
+    pub const MODEL_UNLOCK_1: u8 = 0x62;
+    /// See `MODEL_UNLOCK_1`
+    pub const MODEL_UNLOCK_2: u8 = 0x63;
+
+    /// Model table (80h to AFh)
+    /// The 48 words of characterization data (OCV table, XTable and capacity tables) of a custom
+    /// model. Only accessible while unlocked, see `MODEL_UNLOCK_1`.
+    pub const MODEL_TABLE: u8 = 0x80;
 }
 
 pub struct OutputRegister;
@@ -272,6 +485,22 @@ impl BitField for StatusBitField {
     const REGISTER: u8 = Register::STATUS;
 }
 
+defmt::bitflags! {
+    /// Status2 Register (B0h)
+    /// Register Type: Special
+    /// The Status2 register reports the hibernate state and full detection. Neither flag asserts the
+    /// ALRT pin, so they are read on demand rather than from `Max1726x::handle_alert`.
+    pub struct Status2: u16 {
+        /// Hib: Hibernate Status. This bit is set to 1 while the device is in hibernate mode and 0
+        /// while it is in active mode. See the HibCfg register.
+        const HIB = 1 << 1;
+
+        /// FullDet: Full Detection. This bit is set to 1 when the fuel gauge detects the end of a
+        /// charge cycle and stays set until cleared by software.
+        const FULL_DET = 1 << 5;
+    }
+}
+
 defmt::bitflags! {
     /// FStat Register (3Dh) (page 39)
     /// Register Type: Special
@@ -397,7 +626,7 @@ impl defmt::Format for HibCfg {
 /// format.
 #[bitfield(bits = 16)]
 #[repr(u16)]
-#[derive(Default, Debug)]
+#[derive(Default, Debug, Clone, Copy)]
 pub struct ModelCfg {
     #[skip]
     __: B2,
@@ -436,13 +665,123 @@ impl BitField for ModelCfg {
     const REGISTER: u8 = Register::MODEL_CFG;
 }
 
+impl defmt::Format for ModelCfg {
+    fn format(&self, f: defmt::Formatter) {
+        defmt::write!(
+            f,
+            "ModelCfg: model_id: {}, v_chg: {}, r100: {}, refresh: {}",
+            self.model_id(),
+            self.v_chg(),
+            self.r100(),
+            self.refresh()
+        )
+    }
+}
+
+/// PackCfg Register (BDh)
+/// Register Type: Special
+/// Initial value: 0x3C00 (single cell)
+/// The PackCfg register configures the number of cells in the pack, the enabled temperature
+/// channels, the thermistor type and the charge pump. Configure PackCfg before the model is
+/// loaded (see `Max1726x::ez_config_with_pack_cfg`).
+#[bitfield(bits = 16)]
+#[repr(u16)]
+#[derive(Default, Debug, Clone, Copy)]
+pub struct PackCfg {
+    /// NCELLS: Number of cells in series minus one. NCELLS = 0, the power-up value, is a single
+    /// cell. See `cells` and `with_cells` for the number of cells.
+    pub n_cells: B4,
+
+    #[skip]
+    __: B2,
+
+    /// ChgPump: Charge pump voltage selection. Set according to the number of cells, see the
+    /// datasheet's multi-cell application circuit.
+    pub chg_pump: B2,
+
+    #[skip]
+    __: B1,
+
+    /// ThType: Thermistor type. Set ThType = 0 for a 10kΩ NTC thermistor, ThType = 1 for a 100kΩ
+    /// NTC thermistor.
+    pub th_type: bool,
+
+    /// ChEn: Set ChEn = 1 to enable the charge pump. Required for multi-cell packs.
+    pub ch_en: bool,
+
+    /// TdEn: Set TdEn = 1 to enable the die temperature channel.
+    pub td_en: bool,
+
+    /// A1En: Set A1En = 1 to enable the AIN1 (thermistor 1) temperature channel.
+    pub a1_en: bool,
+
+    /// A2En: Set A2En = 1 to enable the AIN2 (thermistor 2) temperature channel.
+    pub a2_en: bool,
+
+    #[skip]
+    __: B2,
+}
+
+/// The thermistor on the temperature channels. Maps onto the PackCfg ThType bit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, defmt::Format)]
+pub enum ThermistorType {
+    /// 10kΩ NTC thermistor (ThType = 0)
+    Ntc10k,
+    /// 100kΩ NTC thermistor (ThType = 1)
+    Ntc100k,
+}
+
+impl PackCfg {
+    /// Number of cells in series (NCELLS + 1)
+    pub fn cells(&self) -> u8 {
+        self.n_cells() + 1
+    }
+
+    /// Set NCELLS for `cells` cells in series. Panics if `cells` is not 1 to 16, like the
+    /// generated setters do for values that don't fit their field.
+    pub fn with_cells(self, cells: u8) -> Self {
+        assert!(cells > 0, "a pack has at least one cell");
+        self.with_n_cells(cells - 1)
+    }
+
+    /// The thermistor type selected by this configuration
+    pub fn thermistor_type(&self) -> ThermistorType {
+        if self.th_type() {
+            ThermistorType::Ntc100k
+        } else {
+            ThermistorType::Ntc10k
+        }
+    }
+
+    /// Set the ThType bit for the given thermistor type
+    pub fn with_thermistor_type(self, thermistor_type: ThermistorType) -> Self {
+        self.with_th_type(thermistor_type == ThermistorType::Ntc100k)
+    }
+}
+
+impl defmt::Format for PackCfg {
+    fn format(&self, f: defmt::Formatter) {
+        defmt::write!(
+            f,
+            "NCELLS: {}, ChgPump: {}, ThType: {}, ChEn: {}, TdEn: {}, A1En: {}, A2En: {}",
+            self.n_cells(),
+            self.chg_pump(),
+            self.th_type(),
+            self.ch_en(),
+            self.td_en(),
+            self.a1_en(),
+            self.a2_en()
+        )
+    }
+}
+
 /// VEmpty Register (3Ah) (page 28)
 /// Initial Value: 0xA561 (3.3V / 3.88V)
 /// The VEmpty register sets thresholds related to empty detection during operation. Table 11
 /// shows the register format.
 #[bitfield(bits = 16)]
 #[repr(u16)]
-#[derive(Default, Debug)]
+#[derive(Default, Debug, Clone, Copy)]
 pub struct VEmpty {
     /// VR: Recovery Voltage. Sets the voltage level for clearing empty detection. Once the cell voltage
     /// rises above this point, empty voltage detection is re-enabled. A 40mV resolution gives a range
@@ -492,6 +831,266 @@ impl defmt::Format for VEmpty {
     }
 }
 
+/// Config Register (1Dh) (page 35)
+/// Register Type: Special
+/// Initial Value: 0x2210
+/// The Config register holds all shutdown enable, alert enable, and temperature enable control
+/// bits. Writing a bit location enables the corresponding function within one task period.
+///
+/// Bit positions from MSB to LSB (15 to 0):
+/// TSel SS TS VS IS THSH TEn TEx SHDN COMMSH X ETHRM FTHRM Aen Bei Ber
+#[bitfield(bits = 16)]
+#[repr(u16)]
+#[derive(Default, Debug, Clone, Copy)]
+pub struct Config {
+    /// Ber: Enable alert on battery removal when the IC is mounted host side. When Ber = 1, a
+    /// battery-removal condition, as detected by the TH pin voltage, triggers an alert.
+    pub ber: bool,
+
+    /// Bei: Enable alert on battery insertion when the IC is mounted host side. When Bei = 1, a
+    /// battery-insertion condition, as detected by the TH pin voltage, triggers an alert.
+    pub bei: bool,
+
+    /// Aen: Enable alert on fuel-gauge outputs. When Aen = 1, violation of any of the alert threshold
+    /// register values by temperature, voltage, current, or SOC triggers an alert. This bit affects
+    /// the ALRT pin operation only. The Smx, Smn, Tmx, Tmn, Vmx, Vmn, Imx, and Imn bits of the Status
+    /// register are not disabled.
+    pub aen: bool,
+
+    /// FTHRM: Force Thermistor Bias Switch. This allows the host to control the bias of the
+    /// thermistor switch or enable fast detection of battery removal. Set FTHRM = 1 to always enable
+    /// the thermistor bias switch. With a standard 10kΩ thermistor, this adds an additional ~200µA to
+    /// the current drain of the circuit.
+    pub fthrm: bool,
+
+    /// ETHRM: Enable Thermistor. Set to logic 1 to enable the TH pin measurement.
+    pub ethrm: bool,
+
+    #[skip]
+    __: B1,
+
+    /// COMMSH: Communication Shutdown. Set to logic 1 to force the device to enter shutdown mode if
+    /// both SDA and SCL are held low for more than timeout of the ShdnTimer register. This also
+    /// configures the device to wake up on a rising edge of any communication.
+    pub commsh: bool,
+
+    /// SHDN: Shutdown. Write this bit to logic 1 to force a shutdown of the device after timeout of
+    /// the ShdnTimer register (default 45s delay). SHDN is reset to 0 at power-up and upon exiting
+    /// shutdown mode. To command shutdown within 22.5s, write ShdnTimer = 0x001E.
+    pub shdn: bool,
+
+    /// TEx: Temperature External. Set to 1 to allow external temperature measurements to be written
+    /// to Temp from the host. When set to 0, the IC's own measurements are used instead.
+    pub tex: bool,
+
+    /// TEn: Enable Temperature Channel. Set to 1 and set ETHRM or FTHRM to 1 to enable temperature
+    /// measurements selected by Config.TSel.
+    pub ten: bool,
+
+    /// THSH: TH Shutdown. Set to 1 to enable device shutdown when the IC is mounted host side and
+    /// the battery is removed. The IC enters shutdown if the TH pin remains high (TH > VTHRM x 0.9)
+    /// for longer than the timeout of the ShdnTimer register.
+    pub thsh: bool,
+
+    /// IS: Current ALRT Sticky. When IS = 1, current alerts can only be cleared through software.
+    /// When IS = 0, current alerts are cleared automatically when the threshold is no longer
+    /// exceeded.
+    pub is: bool,
+
+    /// VS: Voltage ALRT Sticky. When VS = 1, voltage alerts can only be cleared through software.
+    /// When VS = 0, voltage alerts are cleared automatically when the threshold is no longer
+    /// exceeded.
+    pub vs: bool,
+
+    /// TS: Temperature ALRT Sticky. When TS = 1, temperature alerts can only be cleared through
+    /// software. When TS = 0, temperature alerts are cleared automatically when the threshold is no
+    /// longer exceeded.
+    pub ts: bool,
+
+    /// SS: SOC ALRT Sticky. When SS = 1, SOC alerts can only be cleared through software. When
+    /// SS = 0, SOC alerts are cleared automatically when the threshold is no longer exceeded.
+    pub ss: bool,
+
+    /// TSel: Temperature sensor select. Set to 0 to use the internal die temperature. Set to 1 to
+    /// measure temperature using the external thermistor. Set ETHRM to 1 when TSel is 1.
+    pub tsel: bool,
+}
+
+impl BitField for Config {
+    const REGISTER: u8 = Register::CONFIG;
+}
+
+impl defmt::Format for Config {
+    fn format(&self, f: defmt::Formatter) {
+        defmt::write!(
+            f,
+            "Config: Ber: {}, Bei: {}, Aen: {}, FTHRM: {}, ETHRM: {}, COMMSH: {}, SHDN: {}, TEx: {}, \
+            TEn: {}, THSH: {}, IS: {}, VS: {}, TS: {}, SS: {}, TSel: {}",
+            self.ber(),
+            self.bei(),
+            self.aen(),
+            self.fthrm(),
+            self.ethrm(),
+            self.commsh(),
+            self.shdn(),
+            self.tex(),
+            self.ten(),
+            self.thsh(),
+            self.is(),
+            self.vs(),
+            self.ts(),
+            self.ss(),
+            self.tsel()
+        )
+    }
+}
+
+/// Config2 Register (BBh) (page 36)
+/// Register Type: Special
+/// Initial Value: 0x3658
+/// The Config2 register holds additional alert enables, the firmware restart command and the
+/// power averaging configuration.
+///
+/// Bit positions from MSB to LSB (15 to 0):
+/// POR_CMD 0 AtRateEn DPEn POWR[3:0] dSOCen TAlrtEn LDMdl 1 DRCfg[1:0] 0 0
+#[bitfield(bits = 16)]
+#[repr(u16)]
+#[derive(Default, Debug, Clone, Copy)]
+pub struct Config2 {
+    #[skip]
+    __: B2,
+
+    /// DRCfg: Deep Relax Time Configuration. Sets the time the cell must be relaxed before the fuel
+    /// gauge treats the open-circuit voltage as fully settled.
+    pub dr_cfg: B2,
+
+    #[skip]
+    __: B1,
+
+    /// LDMdl: Host sets this bit to 1 in order to initiate firmware to finish processing a newly
+    /// loaded model. Firmware clears this bit to zero to indicate that model loading is finished.
+    pub ld_mdl: bool,
+
+    /// TAlrtEn: Temperature Alert Enable. Set this bit to 1 to enable temperature based alerts.
+    /// Write this bit to 0 to disable temperature alerts. This bit is set to 1 at power-up.
+    pub t_alrt_en: bool,
+
+    /// dSOCen: SOC Change Alert Enable. Set this bit to 1 to enable the Status.dSOCi bit function.
+    /// Write this bit to 0 to disable the Status.dSOCi bit. This bit is set to 0 at power-up.
+    pub d_so_cen: bool,
+
+    /// POWR: Sets the time constant for the AvgPower register. The default POR value of 0110b
+    /// gives a time constant of 11.25s. The equation setting the period is:
+    /// AvgPower time constant = 45s x 2^(POWR-8)
+    pub powr: B4,
+
+    /// DPEn: Dynamic Power Enable. Set to 1 to enable the dynamic power calculation.
+    pub dp_en: bool,
+
+    /// AtRateEn: AtRate Enable. Set to 1 to enable the AtRate calculations. When disabled, the
+    /// AtRate output registers are not updated, saving processing time.
+    pub at_rate_en: bool,
+
+    #[skip]
+    __: B1,
+
+    /// POR_CMD: Firmware Restart. Set to 1 to restart the firmware operation without a full reset
+    /// of the IC. This bit is cleared to 0 by the firmware once the restart is complete.
+    pub por_cmd: bool,
+}
+
+impl BitField for Config2 {
+    const REGISTER: u8 = Register::CONFIG_2;
+}
+
+impl defmt::Format for Config2 {
+    fn format(&self, f: defmt::Formatter) {
+        defmt::write!(
+            f,
+            "Config2: DRCfg: {}, LDMdl: {}, TAlrtEn: {}, dSOCen: {}, POWR: {}, DPEn: {}, \
+            AtRateEn: {}, POR_CMD: {}",
+            self.dr_cfg(),
+            self.ld_mdl(),
+            self.t_alrt_en(),
+            self.d_so_cen(),
+            self.powr(),
+            self.dp_en(),
+            self.at_rate_en(),
+            self.por_cmd()
+        )
+    }
+}
+
+defmt::bitflags! {
+    /// Alerts that stay set in the Status register until cleared by software. The flag positions
+    /// match the IS, VS, TS and SS bits of the Config register (1Dh).
+    pub struct StickyAlerts: u16 {
+        /// IS: Current ALRT Sticky
+        const CURRENT = 1 << 11;
+        /// VS: Voltage ALRT Sticky
+        const VOLTAGE = 1 << 12;
+        /// TS: Temperature ALRT Sticky
+        const TEMPERATURE = 1 << 13;
+        /// SS: SOC ALRT Sticky
+        const SOC = 1 << 14;
+    }
+}
+
+/// Selects where the Temp register takes its measurements from. Maps onto the Config register
+/// TSel, ETHRM, TEn and TEx bits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, defmt::Format)]
+pub enum TemperatureSource {
+    /// The IC's internal die temperature (TSel = 0, ETHRM = 0, TEn = 1)
+    Die,
+    /// An external thermistor on the TH pin (TSel = 1, ETHRM = 1, TEn = 1)
+    Thermistor,
+    /// Temperature is written to the Temp register by the host (TEx = 1)
+    Host,
+}
+
+impl Config {
+    /// The temperature source selected by this configuration
+    pub fn temperature_source(&self) -> TemperatureSource {
+        if self.tex() {
+            TemperatureSource::Host
+        } else if self.tsel() {
+            TemperatureSource::Thermistor
+        } else {
+            TemperatureSource::Die
+        }
+    }
+
+    /// The alerts that are configured as sticky
+    pub fn sticky_alerts(&self) -> StickyAlerts {
+        StickyAlerts::from_bits_truncate(u16::from(*self))
+    }
+
+    /// Set the IS, VS, TS and SS bits for the given sticky alerts
+    pub fn with_sticky_alerts(self, sticky: StickyAlerts) -> Self {
+        self.with_is(sticky.contains(StickyAlerts::CURRENT))
+            .with_vs(sticky.contains(StickyAlerts::VOLTAGE))
+            .with_ts(sticky.contains(StickyAlerts::TEMPERATURE))
+            .with_ss(sticky.contains(StickyAlerts::SOC))
+    }
+
+    /// Set the TSel, ETHRM, TEn and TEx bits for the given temperature source
+    pub fn with_temperature_source(self, source: TemperatureSource) -> Self {
+        match source {
+            TemperatureSource::Die => self
+                .with_tsel(false)
+                .with_ethrm(false)
+                .with_ten(true)
+                .with_tex(false),
+            TemperatureSource::Thermistor => self
+                .with_tsel(true)
+                .with_ethrm(true)
+                .with_ten(true)
+                .with_tex(false),
+            TemperatureSource::Host => self.with_tex(true),
+        }
+    }
+}
+
 /// Soft-Wakeup (Command Register 60h) (page 42)
 /// Register Type: Special
 /// To wake and exit hibernate:
@@ -527,26 +1126,20 @@ impl BitField for RCell {
 }
 
 impl RCell {
-    /// Convert the register value to milliohms
-    pub fn to_milliohms(&self) -> f32 {
-        (self.resistance() as f32 * 1000.0) / 4096.0
+    /// Convert the register value to ohms
+    pub fn to_ohms(&self) -> Ohms {
+        Ohms(f64::from(self.resistance()) / 4096.0)
     }
 
-    /// Create a new RCell register with the specified resistance in milliohms
-    pub fn from_milliohms(milliohms: f32) -> Self {
-        let resistance = ((milliohms * 4096.0) / 1000.0) as u16;
-        Self::new().with_resistance(resistance)
+    /// Create a new RCell register with the specified resistance, rounded to the nearest LSB
+    pub fn from_ohms(resistance: Ohms) -> Self {
+        Self::new().with_resistance(libm::round(resistance.0 * 4096.0) as u16)
     }
 }
 
 impl defmt::Format for RCell {
     fn format(&self, f: defmt::Formatter) {
-        defmt::write!(
-            f,
-            "RCell: raw: {}, milliohms: {}",
-            self.resistance(),
-            self.to_milliohms()
-        )
+        defmt::write!(f, "RCell: raw: {}, {}", self.resistance(), self.to_ohms())
     }
 }
 
@@ -568,26 +1161,20 @@ impl BitField for VFocv {
 }
 
 impl VFocv {
-    /// Convert the register value to millivolts
-    pub fn to_millivolts(&self) -> f32 {
-        (self.voltage() as f32 * 78.125) / 1000.0
+    /// Convert the register value to volts
+    pub fn to_volts(&self) -> Volts {
+        Volts(f64::from(self.voltage()) * 78.125e-6)
     }
 
-    /// Create a new VFocv register with the specified voltage in millivolts
-    pub fn from_millivolts(millivolts: f32) -> Self {
-        let voltage = ((millivolts * 1000.0) / 78.125) as u16;
-        Self::new().with_voltage(voltage)
+    /// Create a new VFocv register with the specified voltage, rounded to the nearest LSB
+    pub fn from_volts(voltage: Volts) -> Self {
+        Self::new().with_voltage(libm::round(voltage.0 / 78.125e-6) as u16)
     }
 }
 
 impl defmt::Format for VFocv {
     fn format(&self, f: defmt::Formatter) {
-        defmt::write!(
-            f,
-            "VFocv: raw: {}, millivolts: {}",
-            self.voltage(),
-            self.to_millivolts()
-        )
+        defmt::write!(f, "VFocv: raw: {}, {}", self.voltage(), self.to_volts())
     }
 }
 
@@ -612,6 +1199,15 @@ impl BitField for Ttf {
 }
 
 impl Ttf {
+    /// Register value reported when the TTF estimate does not apply
+    pub const NOT_APPLICABLE: u16 = 0xFFFF;
+
+    /// The TTF estimate as a duration, `None` if it does not apply
+    pub fn to_duration(&self) -> Option<Duration> {
+        (self.time() != Self::NOT_APPLICABLE)
+            .then(|| Duration::from_millis(u64::from(self.time()) * 5625))
+    }
+
     /// Convert the register value to seconds
     pub fn to_seconds(&self) -> f32 {
         self.time() as f32 * 5.625
@@ -668,6 +1264,15 @@ impl BitField for Tte {
 }
 
 impl Tte {
+    /// Register value reported when the TTE estimate does not apply
+    pub const NOT_APPLICABLE: u16 = 0xFFFF;
+
+    /// The TTE estimate as a duration, `None` if it does not apply
+    pub fn to_duration(&self) -> Option<Duration> {
+        (self.time() != Self::NOT_APPLICABLE)
+            .then(|| Duration::from_millis(u64::from(self.time()) * 5625))
+    }
+
     /// Convert the register value to seconds
     pub fn to_seconds(&self) -> f32 {
         self.time() as f32 * 5.625
@@ -703,9 +1308,384 @@ impl defmt::Format for Tte {
     }
 }
 
+/// VAlrtTh Register (01h) (page 33)
+/// Register Type: Special
+/// Initial Value: 0xFF00 (Disabled)
+/// The VAlrtTh register sets upper and lower limits that generate an alert if exceeded by the
+/// VCell register value. The upper 8 bits set the maximum value and the lower 8 bits set the
+/// minimum value. Interrupt threshold limits are selectable with 20mV resolution over the full
+/// operating range of the VCell register.
+#[bitfield(bits = 16)]
+#[repr(u16)]
+#[derive(Debug, Clone, Copy)]
+pub struct VAlrtTh {
+    /// VMIN: Minimum voltage reading. An alert is generated if the VCell register reading falls
+    /// below this value. LSB = 20mV
+    pub min: u8,
+    /// VMAX: Maximum voltage reading. An alert is generated if the VCell register reading exceeds
+    /// this value. LSB = 20mV
+    pub max: u8,
+}
+
+impl BitField for VAlrtTh {
+    const REGISTER: u8 = Register::V_ALRT_TH;
+}
+
+impl VAlrtTh {
+    /// VMIN value that never triggers an alert
+    pub const DISABLED_MIN: u8 = 0x00;
+    /// VMAX value that never triggers an alert
+    pub const DISABLED_MAX: u8 = 0xFF;
+
+    /// Both thresholds disabled, as at power-up
+    pub fn disabled() -> Self {
+        Self::new()
+            .with_min(Self::DISABLED_MIN)
+            .with_max(Self::DISABLED_MAX)
+    }
+}
+
+impl Default for VAlrtTh {
+    fn default() -> Self {
+        Self::disabled()
+    }
+}
+
+impl defmt::Format for VAlrtTh {
+    fn format(&self, f: defmt::Formatter) {
+        defmt::write!(f, "VAlrtTh: min: {}, max: {}", self.min(), self.max())
+    }
+}
+
+/// TAlrtTh Register (02h) (page 33)
+/// Register Type: Special
+/// Initial Value: 0x7F80 (Disabled)
+/// The TAlrtTh register sets upper and lower limits that generate an alert if exceeded by the
+/// Temp register value. The upper 8 bits set the maximum value and the lower 8 bits set the
+/// minimum value. Interrupt threshold limits are stored in two's-complement format with 1°C
+/// resolution over the full operating range of the Temp register.
+#[bitfield(bits = 16)]
+#[repr(u16)]
+#[derive(Debug, Clone, Copy)]
+pub struct TAlrtTh {
+    /// TMIN: Minimum temperature reading (signed). An alert is generated if the Temp register
+    /// reading falls below this value. LSB = 1°C
+    pub min: u8,
+    /// TMAX: Maximum temperature reading (signed). An alert is generated if the Temp register
+    /// reading exceeds this value. LSB = 1°C
+    pub max: u8,
+}
+
+impl BitField for TAlrtTh {
+    const REGISTER: u8 = Register::T_ALRT_TH;
+}
+
+impl TAlrtTh {
+    /// TMIN value that never triggers an alert (-128°C)
+    pub const DISABLED_MIN: u8 = 0x80;
+    /// TMAX value that never triggers an alert (127°C)
+    pub const DISABLED_MAX: u8 = 0x7F;
+
+    /// Both thresholds disabled, as at power-up
+    pub fn disabled() -> Self {
+        Self::new()
+            .with_min(Self::DISABLED_MIN)
+            .with_max(Self::DISABLED_MAX)
+    }
+}
+
+impl Default for TAlrtTh {
+    fn default() -> Self {
+        Self::disabled()
+    }
+}
+
+impl defmt::Format for TAlrtTh {
+    fn format(&self, f: defmt::Formatter) {
+        defmt::write!(
+            f,
+            "TAlrtTh: min: {}, max: {}",
+            self.min() as i8,
+            self.max() as i8
+        )
+    }
+}
+
+/// SAlrtTh Register (03h) (page 34)
+/// Register Type: Special
+/// Initial Value: 0xFF00 (Disabled)
+/// The SAlrtTh register sets upper and lower limits that generate an alert if exceeded by
+/// RepSOC. The upper 8 bits set the maximum value and the lower 8 bits set the minimum value.
+/// Interrupt threshold limits are configurable with 1% resolution over the full operating range.
+#[bitfield(bits = 16)]
+#[repr(u16)]
+#[derive(Debug, Clone, Copy)]
+pub struct SAlrtTh {
+    /// SMIN: Minimum state-of-charge reading. An alert is generated if the RepSOC register
+    /// reading falls below this value. LSB = 1%
+    pub min: u8,
+    /// SMAX: Maximum state-of-charge reading. An alert is generated if the RepSOC register
+    /// reading exceeds this value. LSB = 1%
+    pub max: u8,
+}
+
+impl BitField for SAlrtTh {
+    const REGISTER: u8 = Register::S_ALRT_TH;
+}
+
+impl SAlrtTh {
+    /// SMIN value that never triggers an alert
+    pub const DISABLED_MIN: u8 = 0x00;
+    /// SMAX value that never triggers an alert
+    pub const DISABLED_MAX: u8 = 0xFF;
+
+    /// Both thresholds disabled, as at power-up
+    pub fn disabled() -> Self {
+        Self::new()
+            .with_min(Self::DISABLED_MIN)
+            .with_max(Self::DISABLED_MAX)
+    }
+}
+
+impl Default for SAlrtTh {
+    fn default() -> Self {
+        Self::disabled()
+    }
+}
+
+impl defmt::Format for SAlrtTh {
+    fn format(&self, f: defmt::Formatter) {
+        defmt::write!(f, "SAlrtTh: min: {}, max: {}", self.min(), self.max())
+    }
+}
+
+/// IAlrtTh Register (B4h) (page 34)
+/// Register Type: Special
+/// Initial Value: 0x7F80 (Disabled)
+/// The IAlrtTh register sets upper and lower limits that generate an alert if exceeded by the
+/// Current register value. The upper 8 bits set the maximum value and the lower 8 bits set the
+/// minimum value. Interrupt threshold limits are selectable with 400μV/RSENSE resolution over
+/// the full operating range of the Current register.
+#[bitfield(bits = 16)]
+#[repr(u16)]
+#[derive(Debug, Clone, Copy)]
+pub struct IAlrtTh {
+    /// IMIN: Minimum current reading (signed). An alert is generated if the Current register
+    /// reading falls below this value. LSB = 400μV/RSENSE
+    pub min: u8,
+    /// IMAX: Maximum current reading (signed). An alert is generated if the Current register
+    /// reading exceeds this value. LSB = 400μV/RSENSE
+    pub max: u8,
+}
+
+impl BitField for IAlrtTh {
+    const REGISTER: u8 = Register::I_ALRT_TH;
+}
+
+impl IAlrtTh {
+    /// IMIN value that never triggers an alert
+    pub const DISABLED_MIN: u8 = 0x80;
+    /// IMAX value that never triggers an alert
+    pub const DISABLED_MAX: u8 = 0x7F;
+
+    /// Both thresholds disabled, as at power-up
+    pub fn disabled() -> Self {
+        Self::new()
+            .with_min(Self::DISABLED_MIN)
+            .with_max(Self::DISABLED_MAX)
+    }
+}
+
+impl Default for IAlrtTh {
+    fn default() -> Self {
+        Self::disabled()
+    }
+}
+
+impl defmt::Format for IAlrtTh {
+    fn format(&self, f: defmt::Formatter) {
+        defmt::write!(
+            f,
+            "IAlrtTh: min: {}, max: {}",
+            self.min() as i8,
+            self.max() as i8
+        )
+    }
+}
+
+/// MaxMinVolt Register (1Bh)
+/// Register Type: Special
+/// Initial Value: 0x00FF
+/// The MaxMinVolt register maintains the maximum and minimum of VCell register values since
+/// device reset. The upper 8 bits hold the maximum value and the lower 8 bits the minimum value,
+/// with 20mV resolution. At power-up the maximum is set to 0x00 and the minimum to 0xFF, so the
+/// first reading updates both.
+#[bitfield(bits = 16)]
+#[repr(u16)]
+#[derive(Debug, Clone, Copy)]
+pub struct MaxMinVolt {
+    /// MinVCell: Minimum VCell register reading. LSB = 20mV
+    pub min: u8,
+    /// MaxVCell: Maximum VCell register reading. LSB = 20mV
+    pub max: u8,
+}
+
+impl BitField for MaxMinVolt {
+    const REGISTER: u8 = Register::MAX_MIN_VOLT;
+}
+
+impl MaxMinVolt {
+    /// Minimum value at power-up and after a reset
+    pub const RESET_MIN: u8 = 0xFF;
+    /// Maximum value at power-up and after a reset
+    pub const RESET_MAX: u8 = 0x00;
+
+    /// Nothing tracked, as at power-up
+    pub fn reset() -> Self {
+        Self::new()
+            .with_min(Self::RESET_MIN)
+            .with_max(Self::RESET_MAX)
+    }
+}
+
+impl Default for MaxMinVolt {
+    fn default() -> Self {
+        Self::reset()
+    }
+}
+
+impl defmt::Format for MaxMinVolt {
+    fn format(&self, f: defmt::Formatter) {
+        defmt::write!(f, "MaxMinVolt: min: {}, max: {}", self.min(), self.max())
+    }
+}
+
+/// MaxMinCurr Register (1Ch)
+/// Register Type: Special
+/// Initial Value: 0x807F
+/// The MaxMinCurr register maintains the maximum and minimum of Current register values since
+/// device reset. The upper 8 bits hold the maximum value and the lower 8 bits the minimum value,
+/// in two's-complement format with 400μV/RSENSE resolution. At power-up the maximum is set to
+/// 0x80 and the minimum to 0x7F, so the first reading updates both.
+#[bitfield(bits = 16)]
+#[repr(u16)]
+#[derive(Debug, Clone, Copy)]
+pub struct MaxMinCurr {
+    /// MinCurrent: Minimum Current register reading (signed). LSB = 400μV/RSENSE
+    pub min: u8,
+    /// MaxCurrent: Maximum Current register reading (signed). LSB = 400μV/RSENSE
+    pub max: u8,
+}
+
+impl BitField for MaxMinCurr {
+    const REGISTER: u8 = Register::MAX_MIN_CURR;
+}
+
+impl MaxMinCurr {
+    /// Minimum value at power-up and after a reset
+    pub const RESET_MIN: u8 = 0x7F;
+    /// Maximum value at power-up and after a reset
+    pub const RESET_MAX: u8 = 0x80;
+
+    /// Nothing tracked, as at power-up
+    pub fn reset() -> Self {
+        Self::new()
+            .with_min(Self::RESET_MIN)
+            .with_max(Self::RESET_MAX)
+    }
+}
+
+impl Default for MaxMinCurr {
+    fn default() -> Self {
+        Self::reset()
+    }
+}
+
+impl defmt::Format for MaxMinCurr {
+    fn format(&self, f: defmt::Formatter) {
+        defmt::write!(
+            f,
+            "MaxMinCurr: min: {}, max: {}",
+            self.min() as i8,
+            self.max() as i8
+        )
+    }
+}
+
+/// MaxMinTemp Register (1Ah)
+/// Register Type: Special
+/// Initial Value: 0x807F
+/// The MaxMinTemp register maintains the maximum and minimum Temp register values since device
+/// reset. The upper 8 bits hold the maximum value and the lower 8 bits the minimum value, in
+/// two's-complement format with 1°C resolution. At power-up the maximum is set to 0x80 and the
+/// minimum to 0x7F, so the first reading updates both.
+#[bitfield(bits = 16)]
+#[repr(u16)]
+#[derive(Debug, Clone, Copy)]
+pub struct MaxMinTemp {
+    /// MinTemperature: Minimum Temp register reading (signed). LSB = 1°C
+    pub min: u8,
+    /// MaxTemperature: Maximum Temp register reading (signed). LSB = 1°C
+    pub max: u8,
+}
+
+impl BitField for MaxMinTemp {
+    const REGISTER: u8 = Register::MAX_MIN_TEMP;
+}
+
+impl MaxMinTemp {
+    /// Minimum value at power-up and after a reset
+    pub const RESET_MIN: u8 = 0x7F;
+    /// Maximum value at power-up and after a reset
+    pub const RESET_MAX: u8 = 0x80;
+
+    /// Nothing tracked, as at power-up
+    pub fn reset() -> Self {
+        Self::new()
+            .with_min(Self::RESET_MIN)
+            .with_max(Self::RESET_MAX)
+    }
+}
+
+impl Default for MaxMinTemp {
+    fn default() -> Self {
+        Self::reset()
+    }
+}
+
+impl defmt::Format for MaxMinTemp {
+    fn format(&self, f: defmt::Formatter) {
+        defmt::write!(
+            f,
+            "MaxMinTemp: min: {}, max: {}",
+            self.min() as i8,
+            self.max() as i8
+        )
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    #[test]
+    fn pack_cfg() {
+        let pack_cfg = PackCfg::from(0x3C00);
+        assert_eq!(pack_cfg.n_cells(), 0);
+        assert_eq!(pack_cfg.cells(), 1);
+        assert!(pack_cfg.ch_en());
+        assert!(pack_cfg.td_en());
+        assert!(pack_cfg.a1_en());
+        assert!(pack_cfg.a2_en());
+        assert_eq!(pack_cfg.thermistor_type(), ThermistorType::Ntc10k);
+
+        let pack_cfg = pack_cfg
+            .with_cells(3)
+            .with_a2_en(false)
+            .with_thermistor_type(ThermistorType::Ntc100k);
+        assert_eq!(u16::from(pack_cfg), 0x1E02);
+        assert_eq!(pack_cfg.cells(), 3);
+    }
+
     #[test]
     fn hib_cfg_bits() {
         // Test some hand-crafted bits (see the datasheet for more details)
@@ -739,28 +1719,92 @@ mod tests {
         assert!(!model_cfg.r100());
     }
     #[test]
+    fn config_bits() {
+        // Initial value from the datasheet
+        let config = Config::from(0x2210);
+        assert!(config.ts());
+        assert!(config.ten());
+        assert!(config.ethrm());
+        assert!(!config.tsel());
+        assert!(!config.aen());
+        assert!(!config.shdn());
+        assert_eq!(config.temperature_source(), TemperatureSource::Die);
+
+        assert_eq!(config.sticky_alerts(), StickyAlerts::TEMPERATURE);
+
+        let config = config.with_temperature_source(TemperatureSource::Thermistor);
+        assert_eq!(u16::from(config), 0xA210);
+        assert_eq!(config.temperature_source(), TemperatureSource::Thermistor);
+
+        let config = config.with_sticky_alerts(StickyAlerts::VOLTAGE | StickyAlerts::SOC);
+        assert_eq!(u16::from(config), 0xD210);
+    }
+    #[test]
+    fn config_2_bits() {
+        // Initial value from the datasheet
+        let config_2 = Config2::from(0x3658);
+        assert!(!config_2.por_cmd());
+        assert!(config_2.at_rate_en());
+        assert!(config_2.dp_en());
+        assert_eq!(config_2.powr(), 6);
+        assert!(!config_2.d_so_cen());
+        assert!(config_2.t_alrt_en());
+        assert!(!config_2.ld_mdl());
+        assert_eq!(config_2.dr_cfg(), 2);
+
+        // The reserved bits are kept
+        assert_eq!(u16::from(config_2.with_d_so_cen(true)), 0x36D8);
+    }
+    #[test]
+    fn alert_thresholds_disabled() {
+        // Initial values from the datasheet
+        assert_eq!(u16::from(VAlrtTh::default()), 0xFF00);
+        assert_eq!(u16::from(TAlrtTh::default()), 0x7F80);
+        assert_eq!(u16::from(SAlrtTh::default()), 0xFF00);
+        assert_eq!(u16::from(IAlrtTh::default()), 0x7F80);
+
+        let v_alrt_th = VAlrtTh::from(0xD296);
+        assert_eq!(v_alrt_th.min(), 150);
+        assert_eq!(v_alrt_th.max(), 210);
+    }
+    #[test]
+    fn max_min_reset() {
+        // Initial values from the datasheet
+        assert_eq!(u16::from(MaxMinVolt::default()), 0x00FF);
+        assert_eq!(u16::from(MaxMinCurr::default()), 0x807F);
+        assert_eq!(u16::from(MaxMinTemp::default()), 0x807F);
+
+        let max_min_temp = MaxMinTemp::from(0x1EF6);
+        assert_eq!(max_min_temp.min() as i8, -10);
+        assert_eq!(max_min_temp.max() as i8, 30);
+    }
+    #[test]
     fn rcell_conversion() {
         // Test the initial value from datasheet (160mΩ)
         let rcell = RCell::from(0x0290);
-        assert_eq!(rcell.to_milliohms() as u32, 160);
+        assert!((rcell.to_ohms().0 - 0.160).abs() < 1e-3);
 
         // Test roundtrip conversion
-        let rcell = RCell::from_milliohms(160.0);
-        // account for rounding errors
-        assert!(rcell.to_milliohms() > 159.0);
-        assert!(rcell.to_milliohms() < 161.0);
+        let rcell = RCell::from_ohms(Ohms::from_milliohms(160.0));
+        assert!((rcell.to_ohms().0 - 0.160).abs() < 1e-3);
+
+        // Rounds to the nearest LSB: 0.1601Ω is 655.77 LSBs
+        assert_eq!(RCell::from_ohms(Ohms(0.1601)).resistance(), 656);
     }
     #[test]
     fn vfocv_conversion() {
         // Test a known voltage value (e.g., 3.7V = 3700mV)
-        let vfocv = VFocv::from_millivolts(3700.0);
-        assert!((vfocv.to_millivolts() - 3700.0).abs() < 0.1); // Allow for small rounding errors
+        let vfocv = VFocv::from_volts(Volts(3.7));
+        assert!((vfocv.to_volts().0 - 3.7).abs() < 1e-4); // Allow for small rounding errors
 
         // Test roundtrip conversion
-        let test_voltage = 4200.0; // 4.2V
-        let vfocv = VFocv::from_millivolts(test_voltage);
-        let result = vfocv.to_millivolts();
-        assert!((result - test_voltage).abs() < 0.1); // Allow for small rounding errors
+        let test_voltage = Volts(4.2);
+        let vfocv = VFocv::from_volts(test_voltage);
+        let result = vfocv.to_volts();
+        assert!((result.0 - test_voltage.0).abs() < 1e-4); // Allow for small rounding errors
+
+        // Rounds to the nearest LSB: 3.70007V is 47360.9 LSBs
+        assert_eq!(VFocv::from_volts(Volts(3.70007)).voltage(), 47361);
     }
     #[test]
     fn ttf_conversion() {
@@ -0,0 +1,419 @@
+/// Register-level software model of a MAX1726x for host-side testing.
+///
+/// The simulator implements `embedded_hal::i2c::I2c` on address 0x36 on top of a 256-word register
+/// file, and models the parts of the chip the driver depends on:
+/// * POR: `Status` reads 0x8082 and `FStat.DNR` stays set until the data-ready time has elapsed
+/// * `ModelCfg.Refresh` clears itself once the model-refresh time has elapsed
+/// * the Command register soft-wakeup takes the chip out of hibernate
+/// * Config2.POR_CMD restarts the firmware, as a POR
+/// * the model table (80h to AFh) is only accessible while model access is unlocked
+/// * writes to read-only (output) registers are ignored
+///
+/// Time is simulated: the simulator and its delay share a `SimClock`, so a driver waiting on the
+/// delay advances the simulated time instead of sleeping.
+use crate::{
+    comms::ADDR,
+    max17263,
+    model::MODEL_TABLE_LEN,
+    registers::{Config2, FStat, ModelCfg, OutputRegister, Register, SoftWakeup, Status, Status2},
+};
+use core::cell::Cell;
+use embedded_hal::delay::DelayNs;
+use embedded_hal::i2c::{ErrorKind, ErrorType, I2c, NoAcknowledgeSource, Operation};
+
+/// Time from power-up until FStat.DNR clears (datasheet: 710ms)
+const DATA_READY_TIME_US: u64 = 710_000;
+/// Time the simulated model reload takes after ModelCfg.Refresh is set
+const MODEL_REFRESH_TIME_US: u64 = 175_000;
+
+/// Registers the chip calculates itself; writes to these are ignored
+const READ_ONLY_REGISTERS: [u8; 17] = [
+    OutputRegister::REP_CAP,
+    OutputRegister::REP_SOC,
+    Register::AGE,
+    0x08, // Temp
+    0x09, // VCell
+    0x0A, // Current
+    Register::AVG_CURRENT,
+    OutputRegister::TTE,
+    Register::AVG_TA,
+    Register::AVG_V_CELL,
+    Register::TTF,
+    Register::F_STAT,
+    Register::V_FOCV,
+    Register::POWER,
+    Register::AVG_POWER,
+    max17263::registers::Register::BATT,
+    0xB9, // AgeForecast
+];
+
+/// Simulated time, shared between a simulator and its delays
+#[derive(Debug, Default)]
+pub struct SimClock {
+    now_us: Cell<u64>,
+}
+
+impl SimClock {
+    pub const fn new() -> Self {
+        Self {
+            now_us: Cell::new(0),
+        }
+    }
+
+    /// Simulated time since the clock was created, in microseconds
+    pub fn now_us(&self) -> u64 {
+        self.now_us.get()
+    }
+
+    /// Move the simulated time forward
+    pub fn advance_us(&self, us: u64) {
+        self.now_us.set(self.now_us.get() + us);
+    }
+
+    /// A delay that advances this clock instead of sleeping
+    pub fn delay(&self) -> SimDelay<'_> {
+        SimDelay { clock: self }
+    }
+}
+
+/// A `DelayNs` implementation that advances a `SimClock`
+#[derive(Debug, Clone, Copy)]
+pub struct SimDelay<'a> {
+    clock: &'a SimClock,
+}
+
+impl DelayNs for SimDelay<'_> {
+    fn delay_ns(&mut self, ns: u32) {
+        self.clock.advance_us(u64::from(ns).div_ceil(1000));
+    }
+}
+
+#[cfg(feature = "async")]
+impl embedded_hal_async::delay::DelayNs for SimDelay<'_> {
+    async fn delay_ns(&mut self, ns: u32) {
+        self.clock.advance_us(u64::from(ns).div_ceil(1000));
+    }
+}
+
+/// A simulated MAX1726x on the I2C bus
+#[derive(Debug)]
+pub struct Max1726xSimulator<'a> {
+    clock: &'a SimClock,
+    registers: [u16; 256],
+    /// Time from POR until DNR clears, `None` if it never clears
+    data_ready_time_us: Option<u64>,
+    /// Time a model refresh takes, `None` if it never completes
+    model_refresh_time_us: Option<u64>,
+    data_ready_at_us: Option<u64>,
+    model_refresh_done_at_us: Option<u64>,
+    hibernating: bool,
+    connected: bool,
+}
+
+impl<'a> Max1726xSimulator<'a> {
+    /// Create a simulated chip that has just been powered up
+    pub fn new(clock: &'a SimClock) -> Self {
+        let mut simulator = Self {
+            clock,
+            registers: [0; 256],
+            data_ready_time_us: Some(DATA_READY_TIME_US),
+            model_refresh_time_us: Some(MODEL_REFRESH_TIME_US),
+            data_ready_at_us: None,
+            model_refresh_done_at_us: None,
+            hibernating: false,
+            connected: true,
+        };
+        simulator.power_on_reset();
+        simulator
+    }
+
+    /// Reset the register file to its power-up values, as after a hardware or software POR
+    pub fn power_on_reset(&mut self) {
+        self.registers = [0; 256];
+        for (register, value) in [
+            (Register::STATUS, 0x8082),
+            (Register::F_STAT, FStat::DNR.bits()),
+            (Register::HIB_CFG, 0x870C),
+            (Register::DESIGN_CAP, 0x0BB8),
+            (Register::I_CHG_TERM, 0x0640),
+            (Register::V_EMPTY, 0xA561),
+            (Register::MODEL_CFG, 0x0400),
+            (Register::R_CELL, 0x0290),
+            (Register::CONFIG, 0x2210),
+            (Register::CONFIG_2, 0x3658),
+            (Register::V_ALRT_TH, 0xFF00),
+            (Register::T_ALRT_TH, 0x7F80),
+            (Register::S_ALRT_TH, 0xFF00),
+            (Register::I_ALRT_TH, 0x7F80),
+            (Register::MAX_MIN_VOLT, 0x00FF),
+            (Register::MAX_MIN_CURR, 0x807F),
+            (Register::MAX_MIN_TEMP, 0x807F),
+            (Register::R_COMP_0, 0x0070),
+            (Register::TEMP_CO, 0x223E),
+            (Register::FULL_CAP_NOM, 0x1388),
+            (Register::MIX_SOC, 0x3200),
+            (Register::MIX_CAP, 0x09C4),
+            (Register::QR_TABLE_00, 0x1050),
+            (Register::QR_TABLE_10, 0x2012),
+            (Register::QR_TABLE_20, 0x0B04),
+            (Register::QR_TABLE_30, 0x0885),
+            // 1250mAh of 2500mAh at 10mΩ, 3.7V, 25°C, at rest
+            (OutputRegister::REP_CAP, 0x09C4),
+            (OutputRegister::REP_SOC, 0x3200),
+            (0x08, 0x1900),
+            (0x09, 0xB900),
+            (Register::AGE, 0x6400),
+            (Register::FULL_CAP_REP, 0x1388),
+            (OutputRegister::TTE, 0xFFFF),
+            (Register::AVG_TA, 0x1900),
+            (Register::AVG_V_CELL, 0xB900),
+            (Register::TTF, 0xFFFF),
+            (Register::V_FOCV, 0xB900),
+            // Single cell: PackCfg.NCELLS = 0, Batt = VCell
+            (max17263::registers::Register::PACK_CFG, 0x3C00),
+            (max17263::registers::Register::BATT, 0x0B90),
+        ] {
+            self.registers[register as usize] = value;
+        }
+        self.data_ready_at_us = self
+            .data_ready_time_us
+            .map(|time| self.clock.now_us() + time);
+        self.model_refresh_done_at_us = None;
+        self.hibernating = true;
+    }
+
+    /// Set how long FStat.DNR stays set after a POR, `None` for a chip that never becomes ready
+    pub fn set_data_ready_time_ms(&mut self, time_ms: Option<u32>) {
+        self.data_ready_time_us = time_ms.map(|time| u64::from(time) * 1000);
+        if self.registers[Register::F_STAT as usize] & FStat::DNR.bits() != 0 {
+            self.data_ready_at_us = self
+                .data_ready_time_us
+                .map(|time| self.clock.now_us() + time);
+        }
+    }
+
+    /// Set how long a model refresh takes, `None` for a refresh that never completes
+    pub fn set_model_refresh_time_ms(&mut self, time_ms: Option<u32>) {
+        self.model_refresh_time_us = time_ms.map(|time| u64::from(time) * 1000);
+    }
+
+    /// Connect or disconnect the chip from the bus. A disconnected chip NACKs its address.
+    pub fn set_connected(&mut self, connected: bool) {
+        self.connected = connected;
+    }
+
+    /// Whether the chip is in hibernate mode
+    pub fn is_hibernating(&self) -> bool {
+        self.hibernating
+    }
+
+    /// Read a register without going through the bus
+    pub fn register(&self, register: u8) -> u16 {
+        self.registers[register as usize]
+    }
+
+    /// Set a register without going through the bus, including read-only registers
+    pub fn set_register(&mut self, register: u8, value: u16) {
+        self.registers[register as usize] = value;
+    }
+
+    /// Whether model access is unlocked
+    fn model_unlocked(&self) -> bool {
+        self.registers[Register::MODEL_UNLOCK_1 as usize] == 0x0059
+            && self.registers[Register::MODEL_UNLOCK_2 as usize] == 0x00C4
+    }
+
+    fn is_model_table(register: u8) -> bool {
+        (Register::MODEL_TABLE..Register::MODEL_TABLE + MODEL_TABLE_LEN as u8).contains(&register)
+    }
+
+    /// A register read as seen over the bus
+    fn read_word(&self, register: u8) -> u16 {
+        if Self::is_model_table(register) && !self.model_unlocked() {
+            return 0;
+        }
+        if register == Register::STATUS_2 {
+            // Hib follows the hibernate state
+            let status_2 = self.registers[register as usize] & !Status2::HIB.bits();
+            return if self.hibernating {
+                status_2 | Status2::HIB.bits()
+            } else {
+                status_2
+            };
+        }
+        self.registers[register as usize]
+    }
+
+    /// Apply the changes the chip makes on its own as time passes
+    fn update(&mut self) {
+        let now = self.clock.now_us();
+        if self
+            .data_ready_at_us
+            .is_some_and(|ready_at| now >= ready_at)
+        {
+            self.registers[Register::F_STAT as usize] &= !FStat::DNR.bits();
+            self.data_ready_at_us = None;
+        }
+        if self
+            .model_refresh_done_at_us
+            .is_some_and(|done_at| now >= done_at)
+        {
+            let model_cfg = ModelCfg::from(self.registers[Register::MODEL_CFG as usize]);
+            self.registers[Register::MODEL_CFG as usize] = model_cfg.with_refresh(false).into();
+            self.model_refresh_done_at_us = None;
+        }
+    }
+
+    /// A register write as received over the bus
+    fn write_word(&mut self, register: u8, value: u16) {
+        if READ_ONLY_REGISTERS.contains(&register)
+            || (Self::is_model_table(register) && !self.model_unlocked())
+        {
+            return;
+        }
+        let previous = core::mem::replace(&mut self.registers[register as usize], value);
+        match register {
+            Register::MODEL_CFG if ModelCfg::from(value).refresh() => {
+                self.model_refresh_done_at_us = self
+                    .model_refresh_time_us
+                    .map(|time| self.clock.now_us() + time);
+            }
+            Register::SOFT_WAKEUP if value == SoftWakeup::SOFT_WAKEUP => {
+                self.hibernating = false;
+            }
+            Register::CONFIG_2 if Config2::from(value).por_cmd() => {
+                self.power_on_reset();
+            }
+            Register::STATUS if value & !previous & Status::POR.bits() != 0 => {
+                // The host can clear POR but can't raise it, only the chip can
+                self.registers[register as usize] &= !Status::POR.bits();
+            }
+            _ => {}
+        }
+    }
+}
+
+impl ErrorType for Max1726xSimulator<'_> {
+    type Error = ErrorKind;
+}
+
+impl I2c for Max1726xSimulator<'_> {
+    fn transaction(
+        &mut self,
+        address: u8,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        if address != ADDR || !self.connected {
+            return Err(ErrorKind::NoAcknowledge(NoAcknowledgeSource::Address));
+        }
+        self.update();
+        // The register pointer auto-increments after every word for sequential access
+        let mut pointer: u8 = 0;
+        for operation in operations {
+            match operation {
+                Operation::Write(bytes) => {
+                    let Some((&register, data)) = bytes.split_first() else {
+                        continue;
+                    };
+                    pointer = register;
+                    for word in data.chunks_exact(2) {
+                        self.write_word(pointer, u16::from_le_bytes([word[0], word[1]]));
+                        pointer = pointer.wrapping_add(1);
+                    }
+                }
+                Operation::Read(buffer) => {
+                    for word in buffer.chunks_mut(2) {
+                        let bytes = self.read_word(pointer).to_le_bytes();
+                        word.copy_from_slice(&bytes[..word.len()]);
+                        pointer = pointer.wrapping_add(1);
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "async")]
+impl embedded_hal_async::i2c::I2c for Max1726xSimulator<'_> {
+    async fn transaction(
+        &mut self,
+        address: u8,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        I2c::transaction(self, address, operations)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn data_ready_after_power_up() {
+        let clock = SimClock::new();
+        let mut simulator = Max1726xSimulator::new(&clock);
+        let mut data = [0u8; 2];
+        simulator
+            .write_read(ADDR, &[Register::F_STAT], &mut data)
+            .unwrap();
+        assert_eq!(u16::from_le_bytes(data) & FStat::DNR.bits(), 1);
+        clock.delay().delay_ms(710);
+        simulator
+            .write_read(ADDR, &[Register::F_STAT], &mut data)
+            .unwrap();
+        assert_eq!(u16::from_le_bytes(data) & FStat::DNR.bits(), 0);
+    }
+
+    #[test]
+    fn sequential_access() {
+        let clock = SimClock::new();
+        let mut simulator = Max1726xSimulator::new(&clock);
+        simulator
+            .write(ADDR, &[0x40, 0x01, 0x00, 0x02, 0x00])
+            .unwrap();
+        let mut data = [0u8; 4];
+        simulator.write_read(ADDR, &[0x40], &mut data).unwrap();
+        assert_eq!(data, [0x01, 0x00, 0x02, 0x00]);
+    }
+
+    #[test]
+    fn read_only_registers_ignore_writes() {
+        let clock = SimClock::new();
+        let mut simulator = Max1726xSimulator::new(&clock);
+        simulator
+            .write(ADDR, &[OutputRegister::REP_SOC, 0x00, 0x00])
+            .unwrap();
+        assert_eq!(simulator.register(OutputRegister::REP_SOC), 0x3200);
+    }
+
+    #[test]
+    fn model_table_locked() {
+        let clock = SimClock::new();
+        let mut simulator = Max1726xSimulator::new(&clock);
+        let mut data = [0u8; 2];
+        simulator.write(ADDR, &[0x80, 0x34, 0x12]).unwrap();
+        assert_eq!(simulator.register(0x80), 0);
+        simulator.write(ADDR, &[0x62, 0x59, 0x00]).unwrap();
+        simulator.write(ADDR, &[0x63, 0xC4, 0x00]).unwrap();
+        simulator.write(ADDR, &[0x80, 0x34, 0x12]).unwrap();
+        simulator.write_read(ADDR, &[0x80], &mut data).unwrap();
+        assert_eq!(data, [0x34, 0x12]);
+        simulator
+            .write(ADDR, &[0x62, 0x00, 0x00, 0x00, 0x00])
+            .unwrap();
+        simulator.write_read(ADDR, &[0x80], &mut data).unwrap();
+        assert_eq!(data, [0x00, 0x00]);
+        assert_eq!(simulator.register(0x80), 0x1234);
+    }
+
+    #[test]
+    fn wrong_address_is_not_acknowledged() {
+        let clock = SimClock::new();
+        let mut simulator = Max1726xSimulator::new(&clock);
+        assert_eq!(
+            simulator.write(0x0B, &[0x00, 0x00, 0x00]),
+            Err(ErrorKind::NoAcknowledge(NoAcknowledgeSource::Address))
+        );
+    }
+}
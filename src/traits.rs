@@ -1,39 +1,99 @@
+use core::fmt::Debug;
+
+/// The types a `RegisterResolver` converts register values to and from. `units::Float` uses
+/// `f64` quantities, `units::FixedPoint` uses integers in milli- or micro-units.
+pub trait Units {
+    /// Battery capacity
+    type Capacity: Copy + Debug + PartialEq + defmt::Format;
+    /// State of charge and other percentages
+    type Percentage: Copy + Debug + PartialEq + defmt::Format;
+    /// Cell and pack voltage
+    type Voltage: Copy + Debug + PartialEq + defmt::Format;
+    /// Charge (positive) or discharge (negative) current
+    type Current: Copy + Debug + PartialEq + defmt::Format;
+    /// Temperature
+    type Temperature: Copy + Debug + PartialEq + defmt::Format;
+    /// Resistance
+    type Resistance: Copy + Debug + PartialEq + defmt::Format;
+    /// Time
+    type Time: Copy + Debug + PartialEq + defmt::Format;
+    /// Charge (positive) or discharge (negative) power
+    type Power: Copy + Debug + PartialEq + defmt::Format;
+    /// Number of charge/discharge cycles
+    type Cycles: Copy + Debug + PartialEq + defmt::Format;
+}
+
+/// Capacity type of a resolver
+pub type Capacity<R> = <<R as RegisterResolver>::Units as Units>::Capacity;
+/// Percentage type of a resolver
+pub type Percentage<R> = <<R as RegisterResolver>::Units as Units>::Percentage;
+/// Voltage type of a resolver
+pub type Voltage<R> = <<R as RegisterResolver>::Units as Units>::Voltage;
+/// Current type of a resolver
+pub type Current<R> = <<R as RegisterResolver>::Units as Units>::Current;
+/// Temperature type of a resolver
+pub type Temperature<R> = <<R as RegisterResolver>::Units as Units>::Temperature;
+/// Resistance type of a resolver
+pub type Resistance<R> = <<R as RegisterResolver>::Units as Units>::Resistance;
+/// Time type of a resolver
+pub type Time<R> = <<R as RegisterResolver>::Units as Units>::Time;
+/// Power type of a resolver
+pub type Power<R> = <<R as RegisterResolver>::Units as Units>::Power;
+/// Cycles type of a resolver
+pub type Cycles<R> = <<R as RegisterResolver>::Units as Units>::Cycles;
+
 /// Trait for bidirectional conversion between register values and physical measurements
 pub trait RegisterResolver {
-    /// Converts register value to battery capacity in amp-hours (Ah)
-    fn register_to_capacity(&self, register: u16) -> f64;
-    /// Converts battery capacity in amp-hours (Ah) to register value
-    fn capacity_to_register(&self, capacity: f64) -> u16;
+    /// The quantity types the register values are converted to
+    type Units: Units;
+
+    /// Converts register value to battery capacity
+    fn register_to_capacity(&self, register: u16) -> Capacity<Self>;
+    /// Converts battery capacity to register value
+    fn capacity_to_register(&self, capacity: Capacity<Self>) -> u16;
 
     /// Converts register value to state of charge percentage (0-100%)
-    fn register_to_percentage(&self, register: u16) -> f64;
+    fn register_to_percentage(&self, register: u16) -> Percentage<Self>;
     /// Converts state of charge percentage (0-100%) to register value
-    fn percentage_to_register(&self, percentage: f64) -> u16;
-
-    /// Converts register value to voltage in volts (V)
-    fn register_to_voltage(&self, register: u16) -> f64;
-    /// Converts voltage in volts (V) to register value
-    fn voltage_to_register(&self, voltage: f64) -> u16;
-
-    /// Converts register value to current in amperes (A)
-    fn register_to_current(&self, register: u16) -> f64;
-    /// Converts current in amperes (A) to register value
-    fn current_to_register(&self, current: f64) -> u16;
-
-    /// Converts register value to temperature in degrees Celsius (°C)
-    fn register_to_temperature(&self, register: u16) -> f64;
-    /// Converts temperature in degrees Celsius (°C) to register value
-    fn temperature_to_register(&self, temperature: f64) -> u16;
-
-    /// Converts register value to resistance in ohms (Ω)
-    fn register_to_resistance(&self, register: u16) -> f64;
-    /// Converts resistance in ohms (Ω) to register value
-    fn resistance_to_register(&self, resistance: f64) -> u16;
-
-    /// Converts register value to time duration in seconds (s)
-    fn register_to_time(&self, register: u16) -> f64;
-    /// Converts time duration in seconds (s) to register value
-    fn time_to_register(&self, seconds: f64) -> u16;
+    fn percentage_to_register(&self, percentage: Percentage<Self>) -> u16;
+
+    /// Converts register value to voltage
+    fn register_to_voltage(&self, register: u16) -> Voltage<Self>;
+    /// Converts voltage to register value
+    fn voltage_to_register(&self, voltage: Voltage<Self>) -> u16;
+
+    /// Converts register value to current
+    fn register_to_current(&self, register: u16) -> Current<Self>;
+    /// Converts current to register value
+    fn current_to_register(&self, current: Current<Self>) -> u16;
+
+    /// Converts register value to temperature
+    fn register_to_temperature(&self, register: u16) -> Temperature<Self>;
+    /// Converts temperature to register value
+    fn temperature_to_register(&self, temperature: Temperature<Self>) -> u16;
+
+    /// Converts register value to resistance
+    fn register_to_resistance(&self, register: u16) -> Resistance<Self>;
+    /// Converts resistance to register value
+    fn resistance_to_register(&self, resistance: Resistance<Self>) -> u16;
+
+    /// Converts register value to time duration
+    fn register_to_time(&self, register: u16) -> Time<Self>;
+    /// Converts time duration to register value
+    fn time_to_register(&self, time: Time<Self>) -> u16;
+
+    /// Converts register value to power
+    fn register_to_power(&self, register: u16) -> Power<Self>;
+    /// Converts power to register value
+    fn power_to_register(&self, power: Power<Self>) -> u16;
+
+    /// Converts pack voltage register value to voltage
+    fn register_to_pack_voltage(&self, register: u16) -> Voltage<Self>;
+    /// Converts pack voltage to register value
+    fn pack_voltage_to_register(&self, voltage: Voltage<Self>) -> u16;
+
+    /// Converts the Cycles register value (16% of a cycle per LSB) to a number of cycles
+    fn register_to_cycles(&self, register: u16) -> Cycles<Self>;
 }
 
 pub trait Model {
@@ -43,6 +103,44 @@ pub trait Model {
     const CURRENT: u8;
     /// Temp Register
     const TEMP: u8;
+    /// AgeForecast Register, on the parts that forecast the cycle life of the cell
+    const AGE_FORECAST: Option<u8> = None;
+}
+
+/// Defines a part's `Register` type with the VCell, Current and Temp addresses that the
+/// MAX17260, MAX17261, MAX17262 and MAX17265 share. The MAX17263 implements `Model` itself, as
+/// it also has the AgeForecast register.
+macro_rules! model_register {
+    ($part:literal) => {
+        #[doc = concat!("Register addresses of the ", $part)]
+        pub struct Register;
+
+        impl $crate::traits::Model for Register {
+            /// VCell Register (09h)
+            /// Register Type: Voltage
+            /// The VCell register reports the voltage of the cell.
+            const V_CELL: u8 = 0x09;
+            /// Current Register (0Ah)
+            /// Register Type: Current
+            /// The IC measures the voltage across the sense resistor, and the result is stored as
+            /// a two's complement value in the Current register.
+            const CURRENT: u8 = 0x0A;
+            /// Temp Register (08h)
+            /// Register Type: Temperature
+            /// The Temp register provides the temperature measured by the thermistor or die
+            /// temperature based on the Config register setting.
+            const TEMP: u8 = 0x08;
+        }
+    };
+}
+pub(crate) use model_register;
+
+/// Parts that can gauge a multi-cell pack (MAX17261, MAX17263)
+pub trait MultiCell: Model {
+    /// PackCfg Register
+    const PACK_CFG: u8;
+    /// Batt Register
+    const BATT: u8;
 }
 
 pub trait BitField {
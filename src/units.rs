@@ -0,0 +1,283 @@
+/// Physical quantities used by the `RegisterResolver`, the driver getters and the configuration
+/// structs.
+///
+/// Each quantity is a newtype over `f64` in the unit of its name, so a value in mA can't be
+/// passed where A is expected. Use the `from_milli*` constructors for values in milli-units.
+///
+/// On parts without an FPU, the integer quantities in `fixed` avoid floating point altogether.
+use crate::traits::Units;
+use core::fmt;
+use core::time::Duration;
+
+macro_rules! quantity {
+    ($(#[$doc:meta])* $name:ident($repr:ty), $unit:literal $(, $derive:path)*) => {
+        $(#[$doc])*
+        #[derive(Debug, Clone, Copy, Default, PartialEq, PartialOrd, defmt::Format $(, $derive)*)]
+        #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+        pub struct $name(pub $repr);
+
+        impl $name {
+            /// The value in the unit of this quantity
+            pub const fn value(self) -> $repr {
+                self.0
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "{}{}", self.0, $unit)
+            }
+        }
+    };
+}
+
+/// `f64` quantities in SI units, used by the float register resolvers
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, defmt::Format)]
+pub struct Float;
+
+impl Units for Float {
+    type Capacity = MilliAmpHours;
+    type Percentage = Percent;
+    type Voltage = Volts;
+    type Current = Amps;
+    type Temperature = Celsius;
+    type Resistance = Ohms;
+    type Time = Seconds;
+    type Power = Watts;
+    type Cycles = f64;
+}
+
+/// Integer quantities in milli- or micro-units, used by the fixed-point register resolvers
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, defmt::Format)]
+pub struct FixedPoint;
+
+impl Units for FixedPoint {
+    type Capacity = fixed::MilliAmpHours;
+    type Percentage = fixed::MilliPercent;
+    type Voltage = fixed::MilliVolts;
+    type Current = fixed::MicroAmps;
+    type Temperature = fixed::CentiCelsius;
+    type Resistance = fixed::MilliOhms;
+    type Time = fixed::MilliSeconds;
+    type Power = fixed::MilliWatts;
+    type Cycles = fixed::CentiCycles;
+}
+
+quantity!(
+    /// Battery capacity in milliamp-hours
+    MilliAmpHours(f64),
+    "mAh"
+);
+quantity!(
+    /// Voltage in volts
+    Volts(f64),
+    "V"
+);
+quantity!(
+    /// Current in amperes. Positive when charging, negative when discharging.
+    Amps(f64),
+    "A"
+);
+quantity!(
+    /// Temperature in degrees Celsius
+    Celsius(f64),
+    "°C"
+);
+quantity!(
+    /// Resistance in ohms
+    Ohms(f64),
+    "Ω"
+);
+quantity!(
+    /// Percentage, e.g. state of charge. 100% is `Percent(100.0)`.
+    Percent(f64),
+    "%"
+);
+quantity!(
+    /// Time in seconds
+    Seconds(f64),
+    "s"
+);
+quantity!(
+    /// Power in watts. Positive when charging, negative when discharging.
+    Watts(f64),
+    "W"
+);
+
+impl MilliAmpHours {
+    /// Capacity from amp-hours
+    pub fn from_amp_hours(amp_hours: f64) -> Self {
+        Self(amp_hours * 1000.0)
+    }
+
+    /// The capacity in amp-hours
+    pub fn amp_hours(self) -> f64 {
+        self.0 / 1000.0
+    }
+}
+
+impl Volts {
+    /// Voltage from millivolts
+    pub fn from_millivolts(millivolts: f64) -> Self {
+        Self(millivolts / 1000.0)
+    }
+
+    /// The voltage in millivolts
+    pub fn millivolts(self) -> f64 {
+        self.0 * 1000.0
+    }
+}
+
+impl Amps {
+    /// Current from milliamps
+    pub fn from_milliamps(milliamps: f64) -> Self {
+        Self(milliamps / 1000.0)
+    }
+
+    /// The current in milliamps
+    pub fn milliamps(self) -> f64 {
+        self.0 * 1000.0
+    }
+}
+
+impl Ohms {
+    /// Resistance from milliohms
+    pub fn from_milliohms(milliohms: f64) -> Self {
+        Self(milliohms / 1000.0)
+    }
+
+    /// The resistance in milliohms
+    pub fn milliohms(self) -> f64 {
+        self.0 * 1000.0
+    }
+}
+
+impl Seconds {
+    /// The time as a `Duration`. Negative times become zero.
+    pub fn to_duration(self) -> Duration {
+        Duration::from_secs_f64(self.0.max(0.0))
+    }
+}
+
+/// Integer quantities for parts without an FPU. Values are rounded to the nearest unit, with
+/// halves rounded away from zero like `libm::round`.
+pub mod fixed {
+    use core::fmt;
+    use core::time::Duration;
+
+    quantity!(
+        /// Battery capacity in milliamp-hours
+        MilliAmpHours(u32),
+        "mAh",
+        Eq,
+        Ord
+    );
+    quantity!(
+        /// Percentage in thousandths of a percent. 100% is `MilliPercent(100_000)`.
+        MilliPercent(u32),
+        "/1000%",
+        Eq,
+        Ord
+    );
+    quantity!(
+        /// Voltage in millivolts
+        MilliVolts(u32),
+        "mV",
+        Eq,
+        Ord
+    );
+    quantity!(
+        /// Current in microamperes. Positive when charging, negative when discharging.
+        MicroAmps(i32),
+        "µA",
+        Eq,
+        Ord
+    );
+    quantity!(
+        /// Temperature in hundredths of a degree Celsius
+        CentiCelsius(i32),
+        "/100°C",
+        Eq,
+        Ord
+    );
+    quantity!(
+        /// Resistance in milliohms
+        MilliOhms(u32),
+        "mΩ",
+        Eq,
+        Ord
+    );
+    quantity!(
+        /// Resistance in microohms, e.g. of the sense resistor
+        MicroOhms(u32),
+        "µΩ",
+        Eq,
+        Ord
+    );
+    quantity!(
+        /// Time in milliseconds
+        MilliSeconds(u32),
+        "ms",
+        Eq,
+        Ord
+    );
+    quantity!(
+        /// Power in milliwatts. Positive when charging, negative when discharging.
+        MilliWatts(i32),
+        "mW",
+        Eq,
+        Ord
+    );
+    quantity!(
+        /// Number of charge/discharge cycles in hundredths of a cycle
+        CentiCycles(u32),
+        "/100 cycles",
+        Eq,
+        Ord
+    );
+
+    impl MilliSeconds {
+        /// The time as a `Duration`
+        pub fn to_duration(self) -> Duration {
+            Duration::from_millis(u64::from(self.0))
+        }
+    }
+
+    /// Divide, rounding halves away from zero
+    pub(crate) const fn div_round(numerator: i64, denominator: i64) -> i64 {
+        let half = denominator / 2;
+        if (numerator < 0) == (denominator < 0) {
+            (numerator + half) / denominator
+        } else {
+            (numerator - half) / denominator
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn milli_units() {
+        assert_eq!(Volts::from_millivolts(3300.0), Volts(3.3));
+        assert_eq!(Amps::from_milliamps(100.0), Amps(0.1));
+        assert_eq!(MilliAmpHours::from_amp_hours(2.5), MilliAmpHours(2500.0));
+        assert_eq!(Ohms::from_milliohms(10.0).value(), 0.01);
+        assert_eq!(Seconds(5.625).to_duration(), Duration::from_millis(5625));
+        assert_eq!(Seconds(-1.0).to_duration(), Duration::ZERO);
+    }
+
+    #[test]
+    fn div_round() {
+        assert_eq!(fixed::div_round(5, 2), 3);
+        assert_eq!(fixed::div_round(-5, 2), -3);
+        assert_eq!(fixed::div_round(4, 3), 1);
+        assert_eq!(fixed::div_round(-4, 3), -1);
+        assert_eq!(fixed::div_round(7, 10), 1);
+        assert_eq!(
+            fixed::MilliSeconds(5625).to_duration(),
+            Duration::from_millis(5625)
+        );
+    }
+}